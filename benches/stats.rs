@@ -0,0 +1,68 @@
+//! Compares the fixed-size array layout of Stats against the Vec layout it replaced,
+//! where every lookup was a linear find over the stats.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rpg_engine::stats::{Stat, StatGrowth, StatKind, StatName, Stats};
+
+/// The layout Stats used before: one Vec holding every stat, searched by name.
+struct VecStats(Vec<Stat>);
+
+impl VecStats {
+    fn get(&self, name: StatName) -> &Stat {
+        self.0.iter().find(|stat| stat.name == name).unwrap()
+    }
+}
+
+fn stat_list() -> Vec<Stat> {
+    StatName::BUILTIN
+        .iter()
+        .map(|&name| Stat::new(name, StatKind::Static(10), StatGrowth::Medium))
+        .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+    let stats = Stats::new(stat_list());
+    let vec_stats = VecStats(stat_list());
+    let mut group = c.benchmark_group("lookup every stat");
+    group.bench_function("array", |b| {
+        b.iter(|| {
+            StatName::BUILTIN
+                .iter()
+                .map(|&name| stats[black_box(name)].value())
+                .sum::<f64>()
+        })
+    });
+    group.bench_function("vec", |b| {
+        b.iter(|| {
+            StatName::BUILTIN
+                .iter()
+                .map(|&name| vec_stats.get(black_box(name)).value())
+                .sum::<f64>()
+        })
+    });
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let stats = Stats::new(stat_list());
+    let vec_stats = VecStats(stat_list());
+    let mut group = c.benchmark_group("iterate");
+    group.bench_function("array", |b| {
+        b.iter(|| black_box(&stats).iter().map(Stat::value).sum::<f64>())
+    });
+    group.bench_function("vec", |b| {
+        b.iter(|| black_box(&vec_stats).0.iter().map(Stat::value).sum::<f64>())
+    });
+    group.finish();
+}
+
+fn add(c: &mut Criterion) {
+    let stats = Stats::new(stat_list());
+    let bonus = Stats::new(stat_list());
+    c.bench_function("add stats", |b| {
+        b.iter(|| black_box(&stats) + black_box(&bonus))
+    });
+}
+
+criterion_group!(benches, lookup, iterate, add);
+criterion_main!(benches);
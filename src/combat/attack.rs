@@ -0,0 +1,224 @@
+use crate::{
+    effects::StatusKind,
+    entities::{Entity, Row},
+    random,
+    stats::{StatEvent, StatName},
+};
+
+/// The chance of any hit being critical before the crit bonus of the weapon.
+const BASE_CRITICAL_CHANCE: f64 = 0.05;
+/// How much a critical hit multiplies the damage by.
+const CRITICAL_MULTIPLIER: f64 = 1.5;
+/// The lowest chance of landing a hit, no matter how evasive the target is.
+const MIN_HIT_CHANCE: f64 = 0.05;
+/// How much being blind multiplies the chance of landing a hit by.
+const BLIND_ACCURACY: f64 = 0.5;
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents the result of a single hit of an attack.
+pub enum HitOutcome {
+    /// The hit was evaded.
+    Miss,
+    /// The hit landed, dealing the given damage.
+    Hit { damage: usize, critical: bool },
+}
+
+impl HitOutcome {
+    /// Get the damage dealt by this hit.
+    pub fn damage(&self) -> usize {
+        match self {
+            Self::Miss => 0,
+            Self::Hit { damage, .. } => *damage,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents the result of a basic attack after it has been applied to the defender.
+pub struct AttackResult {
+    /// The outcome of every hit of the attack, in order.
+    pub hits: Vec<HitOutcome>,
+    /// The total damage dealt by every hit.
+    pub damage: usize,
+    /// Set if the attack depleted the HealthPoints of the defender.
+    pub event: Option<StatEvent>,
+}
+
+/// Rolls every hit of a basic attack without applying any damage. The attack uses
+/// the weapon of the attacker, or attacks unarmed if there is none:
+/// The chance of hitting is the accuracy of the weapon minus the Evasion of the
+/// defender as a percentage, halved if the attacker is blind.
+/// The damage of a hit is floor((attack + power) - defense / 2), at least 1, where
+/// staves use SpecialAttack and SpecialDefense instead of Attack and Defense. It then
+/// varies by 10% either way and is reduced by the resistance of the defender to the
+/// element of the weapon. Melee damage is lowered if either side is in the back row.
+/// Enemies without a formation stand in Row::Front.
+pub fn roll_attack(
+    attacker: &Entity,
+    attacker_row: Row,
+    defender: &Entity,
+    defender_row: Row,
+) -> Vec<HitOutcome> {
+    let weapon = attacker.equipment().weapon();
+    let row_multiplier =
+        attacker_row.damage_dealt(weapon.category) * defender_row.damage_taken(weapon.category);
+    let (attacking, defending) = if weapon.category.is_special() {
+        (StatName::SpecialAttack, StatName::SpecialDefense)
+    } else {
+        (StatName::Attack, StatName::Defense)
+    };
+    let accuracy = if attacker.statuses.has(StatusKind::Blind) {
+        BLIND_ACCURACY
+    } else {
+        1.0
+    };
+    let (attacker, defender) = (attacker.effective_stats(), defender.effective_stats());
+
+    let attack = attacker.value(attacking) + weapon.power as f64;
+    let defense = defender.value(defending);
+    let resistance = weapon
+        .element
+        .resistance()
+        .map_or(0.0, |name| defender.value(name) / 100.0);
+    let hit_chance = ((weapon.accuracy - defender.value(StatName::Evasion) / 100.0) * accuracy)
+        .max(MIN_HIT_CHANCE);
+
+    (0..weapon.hits)
+        .map(|_| {
+            if !random::chance(hit_chance) {
+                return HitOutcome::Miss;
+            }
+            let variance = random::range(90, 111) as f64 / 100.0;
+            let critical = random::chance(BASE_CRITICAL_CHANCE + weapon.crit_bonus);
+            let mut damage = (attack - defense / 2.0).max(1.0) * variance;
+            damage *= (1.0 - resistance).max(0.0) * row_multiplier;
+            if critical {
+                damage *= CRITICAL_MULTIPLIER;
+            }
+            HitOutcome::Hit {
+                damage: (damage.floor() as usize).max(1),
+                critical,
+            }
+        })
+        .collect()
+}
+
+/// Performs a basic attack of the attacker on the defender, lowering the HealthPoints
+/// of the defender by the damage of every hit. The defender is knocked out if its
+/// HealthPoints run out. Defenders that are not alive are not affected, and no hits
+/// are rolled against them. Whether the attacker is able to act is left to the caller,
+/// see Entity::can_act.
+pub fn basic_attack(
+    attacker: &Entity,
+    attacker_row: Row,
+    defender: &mut Entity,
+    defender_row: Row,
+) -> AttackResult {
+    if !defender.life.is_alive() {
+        return AttackResult {
+            hits: Vec::new(),
+            damage: 0,
+            event: None,
+        };
+    }
+    let hits = roll_attack(attacker, attacker_row, defender, defender_row);
+    let damage = hits.iter().map(HitOutcome::damage).sum();
+    let event = defender.stats[StatName::HealthPoints].damage(damage);
+    defender.check_knock_out();
+    AttackResult {
+        hits,
+        damage,
+        event,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::LifeState,
+        misc::{EquipmentType, ItemKind, Weapon, WeaponCategory},
+        testing,
+    };
+
+    #[test]
+    fn every_hit_of_the_weapon_is_rolled() {
+        let mut attacker = testing::entity("Attacker").build().unwrap();
+        let mut weapon = Weapon::new(WeaponCategory::Dagger, 10);
+        weapon.hits = 3;
+        weapon.accuracy = 1.0;
+        let item = testing::item(ItemKind::Equipment(EquipmentType::Weapon), 1)
+            .weapon(Some(weapon))
+            .build()
+            .unwrap();
+        attacker.equip(item).unwrap();
+        let defender = testing::entity("Defender").build().unwrap();
+
+        let hits = roll_attack(&attacker, Row::Front, &defender, Row::Front);
+        assert_eq!(hits.len(), 3);
+        for hit in hits {
+            // (10 Attack + 10 power) varied by 10%, possibly critical.
+            assert!((18..=33).contains(&hit.damage()), "{:?}", hit);
+        }
+    }
+
+    #[test]
+    fn attacking_unarmed_uses_the_unarmed_profile() {
+        let attacker = testing::entity("Attacker").build().unwrap();
+        let defender = testing::entity("Defender").build().unwrap();
+        assert_eq!(attacker.equipment().weapon(), Weapon::unarmed());
+        let hits = roll_attack(&attacker, Row::Front, &defender, Row::Front);
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn basic_attack_lowers_the_health_of_the_defender() {
+        let attacker = testing::entity("Attacker").build().unwrap();
+        let mut defender = testing::entity("Defender").build().unwrap();
+        let result = basic_attack(&attacker, Row::Front, &mut defender, Row::Front);
+        assert_eq!(
+            result.damage,
+            result.hits.iter().map(HitOutcome::damage).sum()
+        );
+        assert_eq!(
+            defender.stats[StatName::HealthPoints].base(),
+            100 - result.damage
+        );
+    }
+
+    #[test]
+    fn blind_attackers_miss_more_often() {
+        let mut attacker = testing::entity("Attacker").build().unwrap();
+        let mut weapon = Weapon::new(WeaponCategory::Sword, 10);
+        weapon.accuracy = 1.0;
+        let item = testing::item(ItemKind::Equipment(EquipmentType::Weapon), 1)
+            .weapon(Some(weapon))
+            .build()
+            .unwrap();
+        attacker.equip(item).unwrap();
+        attacker
+            .statuses
+            .add(StatusKind::Blind, 3, &mut attacker.stats);
+        let defender = testing::entity("Defender").build().unwrap();
+
+        let misses = (0..1000)
+            .flat_map(|_| roll_attack(&attacker, Row::Front, &defender, Row::Front))
+            .filter(|hit| *hit == HitOutcome::Miss)
+            .count();
+        // Half of the hits miss, give or take far more than chance allows.
+        assert!((350..=650).contains(&misses), "{}", misses);
+    }
+
+    #[test]
+    fn only_alive_defenders_are_attacked() {
+        let attacker = testing::entity("Attacker").build().unwrap();
+        for life in [LifeState::KnockedOut, LifeState::Dead, LifeState::Petrified] {
+            let mut defender = testing::entity("Defender").life(life).build().unwrap();
+            let result = basic_attack(&attacker, Row::Front, &mut defender, Row::Front);
+            assert_eq!(result.damage, 0);
+            assert!(result.hits.is_empty());
+            assert_eq!(defender.stats[StatName::HealthPoints].base(), 100);
+            assert_eq!(defender.life, life);
+        }
+    }
+}
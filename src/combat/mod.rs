@@ -0,0 +1,3 @@
+mod attack;
+
+pub use attack::{basic_attack, roll_attack, AttackResult, HitOutcome};
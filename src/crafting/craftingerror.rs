@@ -0,0 +1,34 @@
+use crate::{
+    ids::{ItemId, TemplateId},
+    misc::ItemError,
+};
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Represents the ways crafting or upgrading can fail before anything is rolled.
+/// Nothing is consumed when one of these is returned.
+pub enum CraftingError {
+    /// The inventory does not hold enough of the material with the given template.
+    MissingMaterial(TemplateId),
+    /// Only equipment can be upgraded.
+    NotUpgradable(ItemId),
+    /// The item has already been upgraded as far as its path goes.
+    MaxUpgrade(ItemId),
+    /// The crafted item could not be put into the inventory.
+    Item(ItemError),
+}
+
+impl fmt::Display for CraftingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMaterial(template) => {
+                write!(f, "There are not enough of material {}", template)
+            }
+            Self::NotUpgradable(id) => write!(f, "Item {} is not equipment", id),
+            Self::MaxUpgrade(id) => write!(f, "Item {} cannot be upgraded any further", id),
+            Self::Item(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for CraftingError {}
@@ -0,0 +1,13 @@
+use crate::ids::ItemId;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Represents what happened when a recipe was crafted or an item was upgraded. The
+/// materials are consumed either way.
+pub enum CraftOutcome {
+    /// The recipe was crafted, and the new item was put in the inventory.
+    Crafted(ItemId),
+    /// The item was upgraded to the given level.
+    Upgraded(u8),
+    /// The roll failed and nothing was made.
+    Failed,
+}
@@ -0,0 +1,26 @@
+mod craftingerror;
+mod craftoutcome;
+mod recipe;
+mod upgrade;
+
+pub use craftingerror::CraftingError;
+pub use craftoutcome::CraftOutcome;
+pub use recipe::{Recipe, RecipeBuilder};
+pub use upgrade::{UpgradeLevel, UpgradePath};
+
+use crate::{ids::TemplateId, misc::Inventory};
+
+/// Finds the first material the inventory does not hold enough of.
+fn missing(inventory: &Inventory, materials: &[(TemplateId, usize)]) -> Option<TemplateId> {
+    materials
+        .iter()
+        .find(|&&(template, quantity)| inventory.count(template) < quantity)
+        .map(|&(template, _)| template)
+}
+
+/// Removes the materials from the inventory, given that it holds enough of them.
+fn consume(inventory: &mut Inventory, materials: &[(TemplateId, usize)]) {
+    for &(template, quantity) in materials {
+        inventory.take(template, quantity);
+    }
+}
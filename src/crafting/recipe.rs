@@ -0,0 +1,93 @@
+use super::{CraftOutcome, CraftingError};
+use crate::{
+    ids::TemplateId,
+    misc::{Inventory, Item},
+    random,
+};
+
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(pattern = "owned")]
+/// Represents a recipe that combines items from the inventory into a new item.
+pub struct Recipe {
+    /// The materials used up by the recipe, as the template of each material and
+    /// how many of it are needed.
+    pub materials: Vec<(TemplateId, usize)>,
+    /// The item made by the recipe. Every item crafted is a new instance of it.
+    pub result: Item,
+    /// The chance of the recipe succeeding, between 0 and 1.
+    #[builder(default = "1.0")]
+    pub chance: f64,
+}
+
+impl Recipe {
+    /// Whether the inventory holds every material of the recipe.
+    pub fn can_craft(&self, inventory: &Inventory) -> bool {
+        super::missing(inventory, &self.materials).is_none()
+    }
+
+    /// Crafts the recipe, using up its materials and putting the new item into the
+    /// inventory if the roll succeeds.
+    pub fn craft(&self, inventory: &mut Inventory) -> Result<CraftOutcome, CraftingError> {
+        if let Some(template) = super::missing(inventory, &self.materials) {
+            return Err(CraftingError::MissingMaterial(template));
+        }
+        if !random::chance(self.chance) {
+            super::consume(inventory, &self.materials);
+            return Ok(CraftOutcome::Failed);
+        }
+        let item = self.result.instantiate();
+        let id = item.id;
+        inventory.add(item).map_err(CraftingError::Item)?;
+        super::consume(inventory, &self.materials);
+        Ok(CraftOutcome::Crafted(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::ItemKind, testing};
+
+    fn recipe(chance: f64) -> Recipe {
+        RecipeBuilder::default()
+            .materials(vec![(TemplateId(1), 2), (TemplateId(2), 1)])
+            .result(
+                testing::item(ItemKind::UsableEverywhere, 3)
+                    .build()
+                    .unwrap(),
+            )
+            .chance(chance)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn crafting_turns_materials_into_the_result() {
+        let mut inventory = testing::materials(&[(1, 3), (2, 1)]);
+        let id = match recipe(1.0).craft(&mut inventory) {
+            Ok(CraftOutcome::Crafted(id)) => id,
+            outcome => panic!("expected the item to be crafted, got {:?}", outcome),
+        };
+        assert_eq!(inventory.get(id).unwrap().template, TemplateId(3));
+        assert_eq!(inventory.count(TemplateId(1)), 1);
+        assert_eq!(inventory.count(TemplateId(2)), 0);
+        assert!(!recipe(1.0).can_craft(&inventory));
+    }
+
+    #[test]
+    fn failing_still_uses_up_the_materials() {
+        let mut inventory = testing::materials(&[(1, 2), (2, 1)]);
+        assert_eq!(recipe(0.0).craft(&mut inventory), Ok(CraftOutcome::Failed));
+        assert!(inventory.is_empty());
+    }
+
+    #[test]
+    fn nothing_is_used_without_every_material() {
+        let mut inventory = testing::materials(&[(1, 2)]);
+        assert_eq!(
+            recipe(1.0).craft(&mut inventory),
+            Err(CraftingError::MissingMaterial(TemplateId(2)))
+        );
+        assert_eq!(inventory.len(), 2);
+    }
+}
@@ -0,0 +1,139 @@
+use super::{CraftOutcome, CraftingError};
+use crate::{
+    ids::TemplateId,
+    misc::{Inventory, Item, ItemKind},
+    random,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents the cost of a single upgrade.
+pub struct UpgradeLevel {
+    /// The materials used up by the upgrade, as the template of each material and
+    /// how many of it are needed.
+    pub materials: Vec<(TemplateId, usize)>,
+    /// The chance of the upgrade succeeding, between 0 and 1.
+    pub chance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents how far equipment can be upgraded and what every upgrade costs. An item
+/// at +N has the stats it had at +0 multiplied by 1 + N * scaling.
+pub struct UpgradePath {
+    /// How much of the stats at +0 every upgrade adds.
+    pub scaling: f64,
+    /// The cost of every upgrade in order: the first is the cost of going from +0 to
+    /// +1. An item cannot be upgraded past the number of levels.
+    pub levels: Vec<UpgradeLevel>,
+}
+
+impl UpgradePath {
+    /// Get the cost of the next upgrade of the item, if it can be upgraded further.
+    pub fn next(&self, item: &Item) -> Option<&UpgradeLevel> {
+        self.levels.get(item.upgrade as usize)
+    }
+
+    /// Get the factor the stats at +0 are multiplied by at the given upgrade.
+    pub fn factor(&self, upgrade: u8) -> f64 {
+        1.0 + upgrade as f64 * self.scaling
+    }
+
+    /// Upgrades a piece of equipment, using up the materials of the next level from the
+    /// inventory and scaling the stats of the item if the roll succeeds. The stats are
+    /// always scaled from the stats at +0, which are kept in Item::base_stats.
+    pub fn upgrade(
+        &self,
+        item: &mut Item,
+        inventory: &mut Inventory,
+    ) -> Result<CraftOutcome, CraftingError> {
+        if !matches!(item.kind, ItemKind::Equipment(_)) {
+            return Err(CraftingError::NotUpgradable(item.id));
+        }
+        let level = self.next(item).ok_or(CraftingError::MaxUpgrade(item.id))?;
+        if let Some(template) = super::missing(inventory, &level.materials) {
+            return Err(CraftingError::MissingMaterial(template));
+        }
+        super::consume(inventory, &level.materials);
+        if !random::chance(level.chance) {
+            return Ok(CraftOutcome::Failed);
+        }
+
+        let base = item.base_stats.take().unwrap_or_else(|| item.stats.clone());
+        item.upgrade += 1;
+        item.stats = &base * self.factor(item.upgrade);
+        item.base_stats = Some(base);
+        Ok(CraftOutcome::Upgraded(item.upgrade))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::EquipmentType, stats::StatName, testing};
+
+    fn path(levels: usize, chance: f64) -> UpgradePath {
+        let level = UpgradeLevel {
+            materials: vec![(TemplateId(1), 1)],
+            chance,
+        };
+        UpgradePath {
+            scaling: 0.5,
+            levels: vec![level; levels],
+        }
+    }
+
+    #[test]
+    fn upgrades_scale_the_stats_at_plus_zero() {
+        let mut sword = testing::equipment(EquipmentType::Weapon, &[(StatName::Attack, 1)]);
+        let mut inventory = testing::materials(&[(1, 3)]);
+        let path = path(3, 1.0);
+        let attack = |sword: &Item| sword.stats.value(StatName::Attack);
+
+        assert_eq!(
+            path.upgrade(&mut sword, &mut inventory),
+            Ok(CraftOutcome::Upgraded(1))
+        );
+        assert_eq!(attack(&sword), 2.0);
+        path.upgrade(&mut sword, &mut inventory).unwrap();
+        assert_eq!(attack(&sword), 2.0);
+        path.upgrade(&mut sword, &mut inventory).unwrap();
+        assert_eq!(attack(&sword), 3.0);
+        assert_eq!(
+            sword.base_stats.as_ref().map(|s| s.value(StatName::Attack)),
+            Some(1.0)
+        );
+        assert_eq!(
+            path.upgrade(&mut sword, &mut inventory),
+            Err(CraftingError::MaxUpgrade(sword.id))
+        );
+    }
+
+    #[test]
+    fn failed_upgrades_use_up_the_materials() {
+        let mut helmet = testing::equipment(EquipmentType::Head, &[(StatName::Defense, 4)]);
+        let mut inventory = testing::materials(&[(1, 1)]);
+        let path = path(1, 0.0);
+        assert_eq!(
+            path.upgrade(&mut helmet, &mut inventory),
+            Ok(CraftOutcome::Failed)
+        );
+        assert_eq!(helmet.upgrade, 0);
+        assert!(inventory.is_empty());
+        assert_eq!(
+            path.upgrade(&mut helmet, &mut inventory),
+            Err(CraftingError::MissingMaterial(TemplateId(1)))
+        );
+    }
+
+    #[test]
+    fn only_equipment_can_be_upgraded() {
+        let mut potion = testing::item(ItemKind::UsableEverywhere, 2)
+            .build()
+            .unwrap();
+        let mut inventory = testing::materials(&[(1, 1)]);
+        assert_eq!(
+            path(1, 1.0).upgrade(&mut potion, &mut inventory),
+            Err(CraftingError::NotUpgradable(potion.id))
+        );
+        assert_eq!(inventory.len(), 1);
+    }
+}
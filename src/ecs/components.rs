@@ -0,0 +1,73 @@
+use crate::{
+    effects::{StatusEffects, TickReport},
+    entities::{Class, Entity, LifeState},
+    ids::EntityId,
+    levels::{LevelData, LevelUpReport},
+    misc::Equipment,
+    skills::SkillSet,
+    stats::Stats,
+};
+use bevy_ecs::{bundle::Bundle, component::Component, event::Event};
+
+#[derive(Component, Debug, Clone, PartialEq)]
+/// The stats of an entity with the bonuses of its equipment applied. Kept up to date by
+/// recompute_effective_stats, and the equivalent of Entity::effective_stats.
+pub struct EffectiveStats(pub Stats);
+
+#[derive(Component, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Experience waiting to be added to an entity. apply_level_ups adds it to the level
+/// data of the entity and resets it to zero.
+pub struct PendingExperience(pub usize);
+
+#[derive(Bundle, Debug, Clone)]
+/// The components that make up a character. Use CharacterBundle::split to move an
+/// Entity into a world.
+pub struct CharacterBundle {
+    pub id: EntityId,
+    pub stats: Stats,
+    pub effective_stats: EffectiveStats,
+    pub level_data: LevelData,
+    pub equipment: Equipment,
+    pub statuses: StatusEffects,
+    pub life: LifeState,
+    pub skills: SkillSet,
+    pub experience: PendingExperience,
+}
+
+impl CharacterBundle {
+    /// Splits an entity into its components. The class of the entity, if it has one, is
+    /// returned separately to be inserted as a component of its own. The name, key,
+    /// tags and class levels of the entity are not components and are dropped.
+    pub fn split(entity: Entity) -> (Self, Option<Class>) {
+        let bundle = Self {
+            id: entity.id(),
+            effective_stats: EffectiveStats(entity.effective_stats()),
+            equipment: entity.equipment().clone(),
+            stats: entity.stats,
+            level_data: entity.level_data,
+            statuses: entity.statuses,
+            life: entity.life,
+            skills: entity.skills,
+            experience: PendingExperience::default(),
+        };
+        (bundle, entity.class)
+    }
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+/// Sent by tick_statuses for every entity whose status effects were ticked.
+pub struct StatusTicked {
+    /// The entity in the world whose statuses were ticked.
+    pub entity: bevy_ecs::entity::Entity,
+    /// What happened to the entity.
+    pub report: TickReport,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+/// Sent by apply_level_ups for every entity that gained experience.
+pub struct LeveledUp {
+    /// The entity in the world that gained experience.
+    pub entity: bevy_ecs::entity::Entity,
+    /// The levels and stats the entity gained.
+    pub report: LevelUpReport,
+}
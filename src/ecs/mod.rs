@@ -0,0 +1,7 @@
+mod components;
+mod systems;
+
+pub use components::{CharacterBundle, EffectiveStats, LeveledUp, PendingExperience, StatusTicked};
+pub use systems::{
+    add_systems, apply_level_ups, recompute_effective_stats, register, tick_statuses,
+};
@@ -0,0 +1,150 @@
+use super::{EffectiveStats, LeveledUp, PendingExperience, StatusTicked};
+use crate::{
+    effects::StatusEffects,
+    entities::{Class, LifeState},
+    ids::EntityId,
+    levels::{self, LevelData},
+    misc::Equipment,
+    skills::SkillSet,
+    stats::Stats,
+};
+use bevy_ecs::{
+    entity::Entity,
+    event::{EventWriter, Events},
+    query::{Changed, Or},
+    schedule::{IntoSystemConfigs, Schedule},
+    system::Query,
+    world::World,
+};
+
+/// Adds the events sent by the systems of this module to the world. Events::update
+/// must be called on both of them once per frame, as bevy_ecs does not do it on its own.
+pub fn register(world: &mut World) {
+    world.init_resource::<Events<StatusTicked>>();
+    world.init_resource::<Events<LeveledUp>>();
+}
+
+/// Adds every system of this module to the schedule, in the order they should run.
+pub fn add_systems(schedule: &mut Schedule) {
+    schedule.add_systems((tick_statuses, apply_level_ups, recompute_effective_stats).chain());
+}
+
+/// Ticks the status effects of every entity, the same as Entity::tick_statuses.
+/// Entities with a LifeState are knocked out if their HealthPoints run out.
+pub fn tick_statuses(
+    mut query: Query<(
+        Entity,
+        &mut StatusEffects,
+        &mut Stats,
+        Option<&mut LifeState>,
+    )>,
+    mut events: EventWriter<StatusTicked>,
+) {
+    for (entity, mut statuses, mut stats, life) in &mut query {
+        let report = statuses.tick(&mut stats);
+        if let Some(mut life) = life {
+            life.check_knock_out(&mut statuses, &mut stats);
+        }
+        events.send(StatusTicked { entity, report });
+    }
+}
+
+/// The components read and changed by apply_level_ups.
+type LevelingUp = (
+    Entity,
+    &'static EntityId,
+    &'static mut PendingExperience,
+    &'static mut LevelData,
+    &'static mut Stats,
+    Option<&'static Class>,
+    Option<&'static mut SkillSet>,
+);
+
+/// Adds the pending experience of every entity to its level data, the same as
+/// Entity::gain_experience. Entities with both a Class and a SkillSet learn the skills
+/// of their class.
+pub fn apply_level_ups(mut query: Query<LevelingUp>, mut events: EventWriter<LeveledUp>) {
+    for (entity, &id, mut pending, mut level_data, mut stats, class, skills) in &mut query {
+        if pending.0 == 0 {
+            continue;
+        }
+        let experience = std::mem::take(&mut pending.0);
+        let report = levels::level_up(
+            id,
+            experience,
+            &mut level_data,
+            &mut stats,
+            class,
+            skills.map(|skills| skills.into_inner()),
+        );
+        events.send(LeveledUp { entity, report });
+    }
+}
+
+/// Matches entities whose stats or equipment changed since the system last ran.
+type StatsChanged = Or<(Changed<Stats>, Changed<Equipment>)>;
+
+/// Recomputes the effective stats of every entity whose stats or equipment changed.
+/// Entities without equipment have the same effective stats as their stats.
+pub fn recompute_effective_stats(
+    mut query: Query<(&Stats, Option<&Equipment>, &mut EffectiveStats), StatsChanged>,
+) {
+    for (stats, equipment, mut effective) in &mut query {
+        effective.0 = match equipment {
+            Some(equipment) => stats + &equipment.stats(),
+            None => stats.clone(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ecs::CharacterBundle, effects::StatusKind, skills::Skill, stats::StatName, testing,
+    };
+
+    fn world() -> (World, Schedule) {
+        let mut world = World::new();
+        register(&mut world);
+        let mut schedule = Schedule::default();
+        add_systems(&mut schedule);
+        (world, schedule)
+    }
+
+    #[test]
+    fn pending_experience_teaches_class_skills() {
+        let (mut world, mut schedule) = world();
+        let fire = Skill::new("Fire", 4, Default::default(), vec![]);
+        let mage = testing::class("Mage")
+            .learnset(vec![(2, fire)])
+            .build()
+            .unwrap();
+        let hero = testing::entity("Hero").with_class(mage).build().unwrap();
+        let (mut bundle, class) = CharacterBundle::split(hero);
+        bundle.experience = PendingExperience(LevelData::experience_for_level(1));
+        let hero = world.spawn((bundle, class.unwrap())).id();
+
+        schedule.run(&mut world);
+        assert_eq!(world.get::<LevelData>(hero).unwrap().level(), 2);
+        assert_eq!(world.get::<PendingExperience>(hero).unwrap().0, 0);
+        assert!(world.get::<SkillSet>(hero).unwrap().get("Fire").is_some());
+        assert_eq!(world.resource::<Events<LeveledUp>>().len(), 1);
+    }
+
+    #[test]
+    fn running_out_of_health_knocks_out() {
+        let (mut world, mut schedule) = world();
+        let (mut bundle, _) = CharacterBundle::split(testing::entity("Hero").build().unwrap());
+        bundle.stats[StatName::HealthPoints].damage(99);
+        bundle
+            .statuses
+            .add(StatusKind::Poison, 3, &mut bundle.stats);
+        let hero = world.spawn(bundle).id();
+
+        schedule.run(&mut world);
+        assert_eq!(world.get::<LifeState>(hero), Some(&LifeState::KnockedOut));
+        assert_eq!(world.get::<StatusEffects>(hero).unwrap().iter().count(), 0);
+        assert_eq!(world.resource::<Events<StatusTicked>>().len(), 1);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    effects::StatusKind,
+    skills::Skill,
+    stats::{Modifier, StatName},
+};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents how much of a stat an effect restores.
+pub enum Amount {
+    /// A fixed amount.
+    Flat(usize),
+    /// A percentage of the maximum value of the stat, between 0 and 100.
+    Percent(f64),
+}
+
+impl Amount {
+    /// Get the fixed amount this represents for a stat with the given maximum value.
+    pub fn resolve(&self, max: usize) -> usize {
+        match *self {
+            Self::Flat(amount) => amount,
+            Self::Percent(percent) => (max as f64 * percent / 100.0).max(0.0).round() as usize,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a single thing that happens to the target of an item or a skill. Both
+/// hold a list of effects that are carried out by the same interpreter, see apply.
+pub enum Effect {
+    /// Heals a depletable stat such as HealthPoints or SkillPoints. Has no effect on
+    /// targets that are not alive.
+    Heal(StatName, Amount),
+    /// Removes a status effect.
+    Cure(StatusKind),
+    /// Removes every ailment, leaving stat modifiers in place.
+    CureAll,
+    /// Applies a modifier to a stat for a number of turns. Mult(0.0) has no effect, as
+    /// it could not be removed once the turns run out.
+    Modify {
+        stat: StatName,
+        modifier: Modifier,
+        turns: u32,
+    },
+    /// Gives experience, which may level up the target.
+    GrantExperience(usize),
+    /// Brings a knocked out target back with the given amount of HealthPoints, such as
+    /// Percent(50.0) for half of them. Has no effect on targets that are not knocked out.
+    Revive(Amount),
+    /// Turns a petrified target back to normal.
+    Unpetrify,
+    /// Raises the Friendship stat of the target.
+    RaiseFriendship(usize),
+    /// Teaches a skill to the target.
+    TeachSkill(Skill),
+}
@@ -0,0 +1,390 @@
+use crate::{
+    effects::{Context, Effect, StatusKind, Target},
+    entities::{Entity, LifeState},
+    levels::LevelUpReport,
+    misc::{Item, UseDenied},
+    skills::{Skill, SkillEvent},
+    stats::{Modifier, Overheal, StatName},
+};
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Clone)]
+/// Represents what a single effect did to its target.
+pub enum EffectOutcome {
+    /// The stat was healed by the given amount.
+    Healed(StatName, usize),
+    /// The statuses were removed.
+    Cured(Vec<StatusKind>),
+    /// A modifier was applied to the stat for the given number of turns.
+    Modified(StatName, u32),
+    /// Experience was gained, possibly levelling up the target.
+    GainedExperience(LevelUpReport),
+    /// The target was revived with the given amount of HealthPoints.
+    Revived(usize),
+    /// The target was turned back from stone.
+    Unpetrified,
+    /// Friendship was raised by the given amount.
+    FriendshipRaised(usize),
+    /// A skill was taught, or needs a free slot to be learned.
+    Skill(SkillEvent),
+    /// The effect did nothing, such as healing a knocked out target.
+    NoEffect,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+/// Represents the reasons an item or skill cannot be used.
+pub enum EffectError {
+    /// The item or skill cannot be used in this context.
+    WrongContext(Context),
+    /// The targets given do not fit the target rule, such as several targets for a
+    /// single target skill.
+    InvalidTargets(Target),
+    /// The user does not have enough SkillPoints.
+    NotEnoughSkillPoints,
+    /// The user is not able to act, such as when it is knocked out or asleep.
+    CannotAct,
+    /// The user is silenced and cannot use skills.
+    Silenced,
+    /// The user is not allowed to use the item.
+    Denied(UseDenied),
+}
+
+impl fmt::Display for EffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongContext(context) => write!(f, "Cannot be used in {:?}", context),
+            Self::InvalidTargets(target) => write!(f, "Invalid targets for {:?}", target),
+            Self::NotEnoughSkillPoints => write!(f, "Not enough SkillPoints"),
+            Self::CannotAct => write!(f, "The user cannot act"),
+            Self::Silenced => write!(f, "The user is silenced"),
+            Self::Denied(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for EffectError {}
+
+/// Carries out a single effect on a target. This is the interpreter shared by items
+/// and skills. Dead targets are not affected by anything, and petrified targets only
+/// by Unpetrify.
+pub fn apply(effect: &Effect, target: &mut Entity) -> EffectOutcome {
+    match (target.life, effect) {
+        (LifeState::Dead, _) => return EffectOutcome::NoEffect,
+        (LifeState::Petrified, Effect::Unpetrify) => {
+            target.life = LifeState::Alive;
+            target.check_knock_out();
+            return EffectOutcome::Unpetrified;
+        }
+        (LifeState::Petrified, _) => return EffectOutcome::NoEffect,
+        _ => {}
+    }
+    match effect {
+        Effect::Heal(name, amount) if target.life.is_alive() => match target.stats.get_mut(*name) {
+            Some(stat) => {
+                let before = stat.base();
+                stat.heal(amount.resolve(stat.max()), Overheal::None);
+                EffectOutcome::Healed(*name, stat.base() - before)
+            }
+            None => EffectOutcome::NoEffect,
+        },
+        Effect::Cure(kind) => {
+            if target.statuses.cure(*kind, &mut target.stats) {
+                EffectOutcome::Cured(vec![*kind])
+            } else {
+                EffectOutcome::NoEffect
+            }
+        }
+        Effect::CureAll => match target.statuses.cure_ailments(&mut target.stats) {
+            cured if cured.is_empty() => EffectOutcome::NoEffect,
+            cured => EffectOutcome::Cured(cured),
+        },
+        // Multiplying by zero cannot be undone once the status expires.
+        Effect::Modify {
+            modifier: Modifier::Mult(0.0),
+            ..
+        } => EffectOutcome::NoEffect,
+        Effect::Modify {
+            stat,
+            modifier,
+            turns,
+        } => {
+            let kind = StatusKind::Modified(*stat, *modifier);
+            target.statuses.add(kind, *turns, &mut target.stats);
+            EffectOutcome::Modified(*stat, *turns)
+        }
+        Effect::GrantExperience(experience) => {
+            EffectOutcome::GainedExperience(target.gain_experience(*experience))
+        }
+        Effect::Revive(amount) => match target.revive(*amount) {
+            Some(health) => EffectOutcome::Revived(health),
+            None => EffectOutcome::NoEffect,
+        },
+        Effect::RaiseFriendship(amount) => {
+            let friendship = &mut target.stats[StatName::Friendship];
+            let before = friendship.base();
+            friendship.set_base(before.saturating_add(*amount).min(friendship.limit()));
+            EffectOutcome::FriendshipRaised(friendship.base() - before)
+        }
+        Effect::TeachSkill(skill) => match target.learn_skill(skill.clone()) {
+            SkillEvent::AlreadyKnown(_) => EffectOutcome::NoEffect,
+            event => EffectOutcome::Skill(event),
+        },
+        _ => EffectOutcome::NoEffect,
+    }
+}
+
+/// Checks that the targets given fit the target rule in the given context.
+fn check_targets(target: Target, context: Context, count: usize) -> Result<(), EffectError> {
+    let count_fits = if target.is_single() {
+        count == 1
+    } else {
+        count > 0
+    };
+    if target.allowed_in(context) && count_fits {
+        Ok(())
+    } else {
+        Err(EffectError::InvalidTargets(target))
+    }
+}
+
+/// Uses an item on the targets, carrying out every effect of the item on each target
+/// in order. The kind of the item decides whether it can be used in the context, and
+/// its restrictions whether the user can use it. The user must be able to act. Items
+/// that target their user ignore targets. The item is not used up: the caller removes
+/// it from the inventory once this succeeds.
+pub fn use_item(
+    item: &Item,
+    user: &mut Entity,
+    context: Context,
+    targets: &mut [&mut Entity],
+) -> Result<Vec<EffectOutcome>, EffectError> {
+    if !item.kind.usable_in(context) {
+        return Err(EffectError::WrongContext(context));
+    } else if !user.can_act() {
+        return Err(EffectError::CannotAct);
+    }
+    user.can_use(item).map_err(EffectError::Denied)?;
+    if item.target == Target::User {
+        return Ok(item
+            .effects
+            .iter()
+            .map(|effect| apply(effect, user))
+            .collect());
+    }
+    check_targets(item.target, context, targets.len())?;
+
+    Ok(targets
+        .iter_mut()
+        .flat_map(|target| {
+            item.effects
+                .iter()
+                .map(|effect| apply(effect, target))
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+/// Uses a skill, spending the SkillPoints of the user and carrying out every effect of
+/// the skill on each target in order. Skills that target their user ignore targets.
+/// The user must be able to act and must not be silenced.
+pub fn use_skill(
+    skill: &Skill,
+    user: &mut Entity,
+    context: Context,
+    targets: &mut [&mut Entity],
+) -> Result<Vec<EffectOutcome>, EffectError> {
+    if !user.can_act() {
+        return Err(EffectError::CannotAct);
+    } else if user.statuses.has(StatusKind::Silence) {
+        return Err(EffectError::Silenced);
+    }
+    if skill.target != Target::User {
+        check_targets(skill.target, context, targets.len())?;
+    }
+    if !user.stats[StatName::SkillPoints].spend(skill.cost) {
+        return Err(EffectError::NotEnoughSkillPoints);
+    }
+
+    if skill.target == Target::User {
+        return Ok(skill
+            .effects
+            .iter()
+            .map(|effect| apply(effect, user))
+            .collect());
+    }
+    Ok(targets
+        .iter_mut()
+        .flat_map(|target| {
+            skill
+                .effects
+                .iter()
+                .map(|effect| apply(effect, target))
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        effects::Amount,
+        misc::{ItemKind, Restriction},
+        testing,
+    };
+
+    fn potion(target: Target, effects: Vec<Effect>) -> Item {
+        testing::item(ItemKind::UsableEverywhere, 1)
+            .target(target)
+            .effects(effects)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn heal_reports_the_amount_healed() {
+        let mut target = testing::entity("Target").build().unwrap();
+        target.stats[StatName::HealthPoints].damage(30);
+        let heal = Effect::Heal(StatName::HealthPoints, Amount::Percent(50.0));
+        assert_eq!(
+            apply(&heal, &mut target),
+            EffectOutcome::Healed(StatName::HealthPoints, 30)
+        );
+        assert_eq!(target.stats[StatName::HealthPoints].base(), 100);
+    }
+
+    #[test]
+    fn friendship_is_capped() {
+        let mut target = testing::entity("Target").build().unwrap();
+        assert_eq!(
+            apply(&Effect::RaiseFriendship(4), &mut target),
+            EffectOutcome::FriendshipRaised(4)
+        );
+        assert_eq!(
+            apply(&Effect::RaiseFriendship(usize::MAX), &mut target),
+            EffectOutcome::FriendshipRaised(11)
+        );
+        assert_eq!(target.stats[StatName::Friendship].base(), 15);
+    }
+
+    #[test]
+    fn items_apply_every_effect_to_every_target() {
+        let mut user = testing::entity("User").build().unwrap();
+        let mut first = testing::entity("First").build().unwrap();
+        let mut second = testing::entity("Second").build().unwrap();
+        let item = potion(
+            Target::AllAllies,
+            vec![Effect::RaiseFriendship(1), Effect::RaiseFriendship(2)],
+        );
+        let outcomes = use_item(
+            &item,
+            &mut user,
+            Context::Field,
+            &mut [&mut first, &mut second],
+        )
+        .unwrap();
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(second.stats[StatName::Friendship].base(), 3);
+    }
+
+    #[test]
+    fn items_that_target_the_user_ignore_targets() {
+        let mut user = testing::entity("User").build().unwrap();
+        let item = potion(Target::User, vec![Effect::RaiseFriendship(2)]);
+        use_item(&item, &mut user, Context::Battle, &mut []).unwrap();
+        assert_eq!(user.stats[StatName::Friendship].base(), 2);
+    }
+
+    #[test]
+    fn items_check_their_context_targets_and_restrictions() {
+        let mut user = testing::entity("User").build().unwrap();
+        let mut target = testing::entity("Target").build().unwrap();
+        let battle = testing::item(ItemKind::UsableInBattle, 1).build().unwrap();
+        assert_eq!(
+            use_item(&battle, &mut user, Context::Field, &mut [&mut target]),
+            Err(EffectError::WrongContext(Context::Field))
+        );
+
+        let single = potion(Target::Ally, vec![]);
+        assert_eq!(
+            use_item(&single, &mut user, Context::Field, &mut []),
+            Err(EffectError::InvalidTargets(Target::Ally))
+        );
+
+        let mut restricted = potion(Target::Ally, vec![]);
+        restricted.restrictions = vec![Restriction::MinLevel(10)];
+        assert_eq!(
+            use_item(&restricted, &mut user, Context::Field, &mut [&mut target]),
+            Err(EffectError::Denied(UseDenied::LevelTooLow(10)))
+        );
+    }
+
+    #[test]
+    fn skills_spend_skill_points() {
+        let mut user = testing::entity("User").build().unwrap();
+        let mut target = testing::entity("Target").build().unwrap();
+        let skill = Skill::new("Charm", 15, Target::Ally, vec![Effect::RaiseFriendship(1)]);
+        assert!(use_skill(&skill, &mut user, Context::Field, &mut [&mut target]).is_ok());
+        assert_eq!(user.stats[StatName::SkillPoints].base(), 5);
+        assert_eq!(
+            use_skill(&skill, &mut user, Context::Field, &mut [&mut target]),
+            Err(EffectError::NotEnoughSkillPoints)
+        );
+    }
+
+    #[test]
+    fn knocked_out_users_cannot_act() {
+        let mut user = testing::entity("User").build().unwrap();
+        let mut target = testing::entity("Target").build().unwrap();
+        user.stats[StatName::HealthPoints].damage(100);
+        user.check_knock_out();
+
+        let item = potion(Target::Ally, vec![Effect::RaiseFriendship(1)]);
+        assert_eq!(
+            use_item(&item, &mut user, Context::Field, &mut [&mut target]),
+            Err(EffectError::CannotAct)
+        );
+        let skill = Skill::new("Charm", 1, Target::Ally, vec![]);
+        assert_eq!(
+            use_skill(&skill, &mut user, Context::Field, &mut [&mut target]),
+            Err(EffectError::CannotAct)
+        );
+    }
+
+    #[test]
+    fn revive_brings_back_the_knocked_out() {
+        let mut target = testing::entity("Target").build().unwrap();
+        let revive = Effect::Revive(Amount::Percent(25.0));
+        assert_eq!(apply(&revive, &mut target), EffectOutcome::NoEffect);
+
+        target.stats[StatName::HealthPoints].damage(100);
+        target.check_knock_out();
+        assert_eq!(apply(&revive, &mut target), EffectOutcome::Revived(25));
+        assert_eq!(target.life, LifeState::Alive);
+    }
+
+    #[test]
+    fn silenced_users_cannot_use_skills() {
+        let mut user = testing::entity("User").build().unwrap();
+        user.statuses.add(StatusKind::Silence, 2, &mut user.stats);
+        let skill = Skill::new("Focus", 5, Target::User, vec![]);
+        assert_eq!(
+            use_skill(&skill, &mut user, Context::Battle, &mut []),
+            Err(EffectError::Silenced)
+        );
+        assert_eq!(user.stats[StatName::SkillPoints].base(), 20);
+    }
+
+    #[test]
+    fn modifiers_that_cannot_be_undone_are_rejected() {
+        let mut target = testing::entity("Target").build().unwrap();
+        let zero = Effect::Modify {
+            stat: StatName::Attack,
+            modifier: Modifier::Mult(0.0),
+            turns: 2,
+        };
+        assert_eq!(apply(&zero, &mut target), EffectOutcome::NoEffect);
+        assert_eq!(target.stats.value(StatName::Attack), 10.0);
+        assert_eq!(target.statuses.iter().count(), 0);
+    }
+}
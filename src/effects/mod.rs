@@ -0,0 +1,9 @@
+mod effect;
+mod interpreter;
+mod status;
+mod target;
+
+pub use effect::{Amount, Effect};
+pub use interpreter::{apply, use_item, use_skill, EffectError, EffectOutcome};
+pub use status::{StatusEffect, StatusEffects, StatusKind, TickReport};
+pub use target::{Context, Target};
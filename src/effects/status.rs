@@ -0,0 +1,156 @@
+use crate::stats::{Modifier, StatEvent, StatName, Stats};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents the kinds of status effects an entity can be under.
+pub enum StatusKind {
+    /// Loses a sixteenth of its maximum HealthPoints every turn.
+    Poison,
+    /// Cannot act.
+    Sleep,
+    /// Cannot act.
+    Paralysis,
+    /// Cannot use skills.
+    Silence,
+    /// Attacks are half as accurate.
+    Blind,
+    /// A temporary buff or debuff. The modifier is applied to the stat while the
+    /// status lasts and removed once it expires.
+    Modified(StatName, Modifier),
+}
+
+impl StatusKind {
+    /// Whether this status is an ailment, as opposed to a temporary stat modifier.
+    pub fn is_ailment(&self) -> bool {
+        !matches!(self, Self::Modified(_, _))
+    }
+
+    /// Whether an entity under this status is unable to act.
+    pub fn prevents_action(&self) -> bool {
+        matches!(self, Self::Sleep | Self::Paralysis)
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents a status effect along with how many turns it has left.
+pub struct StatusEffect {
+    /// The kind of status.
+    pub kind: StatusKind,
+    /// The number of turns left before the status wears off.
+    pub turns: u32,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+/// Represents what happened to an entity when its status effects were ticked.
+pub struct TickReport {
+    /// The damage dealt by statuses such as Poison.
+    pub damage: usize,
+    /// Set if the damage depleted the HealthPoints of the entity.
+    pub event: Option<StatEvent>,
+    /// The statuses that wore off.
+    pub expired: Vec<StatusKind>,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents every status effect an entity is under. Statuses that modify stats are
+/// applied to the stats passed in when they are added, and removed when they expire
+/// or are cured.
+pub struct StatusEffects(Vec<StatusEffect>);
+
+impl StatusEffects {
+    /// Maximum HealthPoints are divided by this to get the damage Poison deals every turn.
+    const POISON_DIVISOR: usize = 16;
+
+    /// Puts the entity under a status for a number of turns. If it is already under
+    /// that status, the longer duration is kept instead of stacking.
+    pub fn add(&mut self, kind: StatusKind, turns: u32, stats: &mut Stats) {
+        match self.0.iter_mut().find(|status| status.kind == kind) {
+            Some(status) => status.turns = status.turns.max(turns),
+            None => {
+                if let StatusKind::Modified(name, modifier) = kind {
+                    stats.add_modifier(name, modifier);
+                }
+                self.0.push(StatusEffect { kind, turns });
+            }
+        }
+    }
+
+    /// Removes a status. Returns false if the entity was not under it.
+    pub fn cure(&mut self, kind: StatusKind, stats: &mut Stats) -> bool {
+        match self.0.iter().position(|status| status.kind == kind) {
+            Some(index) => {
+                Self::expire(self.0.remove(index).kind, stats);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every ailment, returning the ones that were removed.
+    pub fn cure_ailments(&mut self, stats: &mut Stats) -> Vec<StatusKind> {
+        let ailments = self
+            .0
+            .iter()
+            .map(|status| status.kind)
+            .filter(StatusKind::is_ailment)
+            .collect::<Vec<_>>();
+        ailments.iter().for_each(|&kind| {
+            self.cure(kind, stats);
+        });
+        ailments
+    }
+
+    /// Removes every status, including stat modifiers. Used at the end of a battle.
+    pub fn clear(&mut self, stats: &mut Stats) {
+        self.0
+            .drain(..)
+            .for_each(|status| Self::expire(status.kind, stats));
+    }
+
+    /// Whether the entity is under the given status.
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.0.iter().any(|status| status.kind == kind)
+    }
+
+    /// Whether any status keeps the entity from acting.
+    pub fn prevents_action(&self) -> bool {
+        self.0.iter().any(|status| status.kind.prevents_action())
+    }
+
+    /// Iterates over every status.
+    pub fn iter(&self) -> std::slice::Iter<'_, StatusEffect> {
+        self.0.iter()
+    }
+
+    /// Advances every status by one turn. Poison deals its damage, and statuses that
+    /// run out of turns wear off.
+    pub fn tick(&mut self, stats: &mut Stats) -> TickReport {
+        let mut report = TickReport::default();
+        if self.has(StatusKind::Poison) {
+            let health = &mut stats[StatName::HealthPoints];
+            report.damage = (health.max() / Self::POISON_DIVISOR).max(1);
+            report.event = health.damage(report.damage);
+        }
+
+        self.0
+            .iter_mut()
+            .for_each(|status| status.turns = status.turns.saturating_sub(1));
+        let (expired, active) = self.0.drain(..).partition(|status| status.turns == 0);
+        self.0 = active;
+        report.expired = expired
+            .into_iter()
+            .map(|status: StatusEffect| {
+                Self::expire(status.kind, stats);
+                status.kind
+            })
+            .collect();
+        report
+    }
+
+    /// Undoes the changes a status made to the stats.
+    fn expire(kind: StatusKind, stats: &mut Stats) {
+        if let StatusKind::Modified(name, modifier) = kind {
+            stats.remove_modifier(name, modifier);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents where an item or skill is being used.
+pub enum Context {
+    Battle,
+    Field,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents who an item or skill can be used on.
+pub enum Target {
+    /// Only the one using the item or skill.
+    User,
+    /// A single party member.
+    #[default]
+    Ally,
+    /// Every party member.
+    AllAllies,
+    /// A single enemy. Only possible in battle.
+    Enemy,
+    /// Every enemy. Only possible in battle.
+    AllEnemies,
+}
+
+impl Target {
+    /// Whether this targets exactly one entity.
+    pub fn is_single(&self) -> bool {
+        matches!(self, Self::User | Self::Ally | Self::Enemy)
+    }
+
+    /// Whether this target can be chosen in the given context. Enemies can only be
+    /// targeted in battle.
+    pub fn allowed_in(&self, context: Context) -> bool {
+        match self {
+            Self::Enemy | Self::AllEnemies => context == Context::Battle,
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+use crate::{
+    misc::{EquipmentType, Item, WeaponCategory},
+    skills::Skill,
+    stats::{StatGrowth, StatName, Stats},
+};
+
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(pattern = "owned")]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents a character class, or job, such as Mage or Knight. A class bundles the
+/// base stats of its members, how fast each stat grows, the skills learned by level
+/// and the equipment its members are allowed to wear. It is applied to an entity
+/// through EntityBuilder::with_class or Entity::change_class.
+pub struct Class {
+    /// The ingame name of the class. Classes with the same name are the same class.
+    #[builder(setter(into))]
+    pub name: Cow<'static, str>,
+    /// The stats a new member of this class starts with.
+    pub base_stats: Stats,
+    /// How fast each stat grows on level up. Stats not listed keep their growth.
+    #[builder(default)]
+    pub growth: Vec<(StatName, StatGrowth)>,
+    /// The skills learned by members of this class, each paired with the level at
+    /// which it is learned.
+    #[builder(default)]
+    pub learnset: Vec<(u8, Skill)>,
+    /// The slots members of this class can equip items into. Every slot is allowed
+    /// if this is None.
+    #[builder(default)]
+    pub equipment: Option<Vec<EquipmentType>>,
+    /// The weapons members of this class can wield. Every weapon is allowed if this
+    /// is None.
+    #[builder(default)]
+    pub weapons: Option<Vec<WeaponCategory>>,
+}
+
+impl Class {
+    /// Get the base stats of this class with the growth of the class applied.
+    pub fn stats(&self) -> Stats {
+        let mut stats = self.base_stats.clone();
+        self.apply_growth(&mut stats);
+        stats
+    }
+
+    /// Sets the growth of every stat listed by this class.
+    pub fn apply_growth(&self, stats: &mut Stats) {
+        for &(name, growth) in &self.growth {
+            if let Some(stat) = stats.get_mut(name) {
+                stat.stat_growth = growth;
+            }
+        }
+    }
+
+    /// Get every skill learned at exactly the given level.
+    pub fn skills_at(&self, level: u8) -> impl Iterator<Item = &Skill> {
+        self.learnset
+            .iter()
+            .filter(move |(learned_at, _)| *learned_at == level)
+            .map(|(_, skill)| skill)
+    }
+
+    /// Whether members of this class are allowed to equip the item.
+    pub fn can_equip(&self, item: &Item) -> bool {
+        let slot_allowed = match (&self.equipment, item.slot()) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(slots), Some(slot)) => slots.contains(&slot),
+        };
+        let weapon_allowed = match (&self.weapons, item.weapon) {
+            (Some(weapons), Some(weapon)) => weapons.contains(&weapon.category),
+            _ => true,
+        };
+        slot_allowed && weapon_allowed
+    }
+}
@@ -0,0 +1,29 @@
+use super::Entity;
+use crate::loot::{Drops, LootTable};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a kind of enemy, such as Slime. Every enemy met in battle is spawned
+/// from its template, and rolls the loot table of the template when defeated.
+pub struct Enemy {
+    /// The entity every spawned enemy starts as.
+    pub template: Entity,
+    /// The rewards for defeating the enemy.
+    pub loot: LootTable,
+}
+
+impl Enemy {
+    /// Create a new enemy template given its entity and loot table.
+    pub fn new(template: Entity, loot: LootTable) -> Self {
+        Self { template, loot }
+    }
+
+    /// Creates an entity to fight from this template, with an id of its own.
+    pub fn spawn(&self) -> Entity {
+        self.template.instantiate()
+    }
+
+    /// Rolls the rewards for defeating an enemy of this template.
+    pub fn drops(&self) -> Drops {
+        self.loot.roll()
+    }
+}
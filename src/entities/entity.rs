@@ -1,20 +1,33 @@
 #![allow(dead_code)]
 
+use super::{Class, LifeState};
 use crate::{
-    levels::LevelData,
-    misc::{Equipment, Item},
-    stats::Stats,
+    effects::{Amount, StatusEffects, TickReport},
+    ids::EntityId,
+    levels::{self, LevelData, LevelUpReport},
+    misc::{Equipment, EquipmentType, Item, UseDenied},
+    skills::{Skill, SkillEvent, SkillSet},
+    stats::{Overheal, StatName, Stats},
 };
+use std::{borrow::Cow, collections::BTreeMap};
 
 #[derive(Builder, Debug, Clone, PartialEq)]
 #[builder(pattern = "owned")]
 /// Represents an entity in the world. This entity can be a user controller player,
 /// an npc, an enemy or any other thing.
 pub struct Entity {
-    /// The name of the entity as displayed in the game.
-    pub name: &'static str,
-    /// The id of the entity assigned on creation.
-    pub id: usize,
+    /// The name of the entity as displayed in the game. Can be set at runtime, such as
+    /// when the player names a character.
+    #[builder(setter(into))]
+    pub name: Cow<'static, str>,
+    /// The key used to look up the name in other languages. The name is shown as is if
+    /// the key is empty or missing.
+    #[builder(default, setter(into))]
+    pub key: Cow<'static, str>,
+    /// The id of the entity assigned on creation. Generated if not set, and cannot be
+    /// changed afterwards.
+    #[builder(default = "EntityId::generate()")]
+    id: EntityId,
     /// The stats of an entity in battle. Only useful for players and enemies.
     pub stats: Stats,
     /// The level data of an entity. This stores the current level and all metadata
@@ -22,18 +35,408 @@ pub struct Entity {
     /// actions.
     pub level_data: LevelData,
     /// The equipment worn by an entity. This determines certain bonuses in stats for an
-    /// entity in battle.
-    pub equipment: Equipment,
+    /// entity in battle. Changed through equip and unequip.
+    equipment: Equipment,
+    /// Whether the equipment cannot be changed, such as for guests of a party.
+    #[builder(setter(skip))]
+    equipment_locked: bool,
+    /// The class of the entity, if it has one. This decides how its stats grow, the
+    /// skills it learns and the equipment it can wear.
+    #[builder(default)]
+    pub class: Option<Class>,
+    /// The level data the entity had in every class it has left, keyed by the name of
+    /// the class. Restored when the entity changes back to that class.
+    #[builder(default)]
+    pub class_levels: BTreeMap<Cow<'static, str>, LevelData>,
+    /// Free-form tags such as "Human" or "Undead". Used to restrict items.
+    #[builder(default)]
+    pub tags: Vec<Cow<'static, str>>,
+    /// The status effects the entity is under, including temporary stat modifiers.
+    #[builder(default)]
+    pub statuses: StatusEffects,
+    /// Whether the entity is alive, knocked out, dead or petrified.
+    #[builder(default)]
+    pub life: LifeState,
+    /// The skills the entity knows, and how many it can know at once.
+    #[builder(default)]
+    pub skills: SkillSet,
 }
 
 impl Entity {
-    /// Determines whether an entity can use a specific item or not. This is determined
-    /// by checking if the entity is in item.restriction.
-    pub fn can_use(&self, item: Item) -> bool {
-        if let Some(restrictions) = item.restriction {
-            restrictions.contains(&self)
+    /// The key in class_levels of the level data the entity had before it was first
+    /// given a class.
+    pub const NO_CLASS: &'static str = "";
+
+    /// Creates a copy of this entity with an id of its own.
+    pub fn instantiate(&self) -> Self {
+        Self {
+            id: EntityId::generate(),
+            ..self.clone()
+        }
+    }
+
+    /// Get the id of the entity.
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+
+    /// Get the equipment worn by the entity.
+    pub fn equipment(&self) -> &Equipment {
+        &self.equipment
+    }
+
+    /// Whether the equipment of the entity cannot be changed.
+    pub fn equipment_locked(&self) -> bool {
+        self.equipment_locked
+    }
+
+    /// Locks or unlocks the equipment of the entity. Party does this for its guests.
+    pub(crate) fn set_equipment_locked(&mut self, locked: bool) {
+        self.equipment_locked = locked;
+    }
+
+    /// Changes the entity through the closure, returning what the closure returns. The
+    /// entity keeps its id and whether its equipment is locked, along with the equipment
+    /// itself while it is locked, even if the closure replaces the whole entity. Party
+    /// hands out its members through this.
+    pub(crate) fn modify<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let (id, locked) = (self.id, self.equipment_locked);
+        let equipment = if locked {
+            Some(self.equipment.clone())
         } else {
-            true
+            None
+        };
+        let result = f(self);
+        self.id = id;
+        self.equipment_locked = locked;
+        if let Some(equipment) = equipment {
+            self.equipment = equipment;
         }
+        result
+    }
+
+    /// Get the localization key of the name of this entity: entity.<key>.name.
+    /// Entities without a key have none.
+    pub fn name_key(&self) -> Option<String> {
+        (!self.key.is_empty()).then(|| format!("entity.{}.name", self.key))
+    }
+
+    /// Whether the entity can take its turn. It must be alive and free of statuses
+    /// that prevent action, such as Sleep.
+    pub fn can_act(&self) -> bool {
+        self.life.can_act() && !self.statuses.prevents_action()
+    }
+
+    /// Knocks out the entity if it is alive but its HealthPoints have run out. Its
+    /// statuses are cleared. Returns whether it was knocked out.
+    pub fn check_knock_out(&mut self) -> bool {
+        self.life
+            .check_knock_out(&mut self.statuses, &mut self.stats)
+    }
+
+    /// Brings a knocked out entity back with the given amount of its HealthPoints,
+    /// at least 1. Returns the HealthPoints it was revived with, or None if it was not
+    /// knocked out or has no maximum HealthPoints to come back with.
+    pub fn revive(&mut self, amount: Amount) -> Option<usize> {
+        let health = &mut self.stats[StatName::HealthPoints];
+        if !self.life.is_revivable() || health.max() == 0 {
+            return None;
+        }
+        health.heal(amount.resolve(health.max()).max(1), Overheal::None);
+        self.life = LifeState::Alive;
+        Some(health.base())
+    }
+
+    /// Ticks the status effects of the entity, knocking it out if they deplete its
+    /// HealthPoints.
+    pub fn tick_statuses(&mut self) -> TickReport {
+        let report = self.statuses.tick(&mut self.stats);
+        self.check_knock_out();
+        report
+    }
+
+    /// Get the stats of this entity with the bonuses of its equipment applied.
+    pub fn effective_stats(&self) -> Stats {
+        &self.stats + &self.equipment.stats()
+    }
+
+    /// Determines whether an entity can use a specific item or not. This is determined
+    /// by checking every restriction of the item, returning the first one that fails.
+    pub fn can_use(&self, item: &Item) -> Result<(), UseDenied> {
+        item.restrictions
+            .iter()
+            .try_for_each(|restriction| restriction.check(self))
+    }
+
+    /// Determines whether an entity can equip a specific item. On top of the
+    /// restrictions of the item, the class of the entity must allow it.
+    pub fn can_equip(&self, item: &Item) -> Result<(), UseDenied> {
+        self.can_use(item)?;
+        match &self.class {
+            Some(class) if !class.can_equip(item) => {
+                Err(UseDenied::CannotEquip(class.name.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Equips an item if the entity is allowed to, returning the item that was
+    /// previously equipped in that slot.
+    pub fn equip(&mut self, item: Item) -> Result<Option<Item>, UseDenied> {
+        if self.equipment_locked {
+            return Err(UseDenied::EquipmentLocked);
+        }
+        self.can_equip(&item)?;
+        self.equipment.equip(item).map_err(UseDenied::Invalid)
+    }
+
+    /// Unequips and returns the item in the given slot, unless the equipment is locked.
+    pub fn unequip(&mut self, slot: EquipmentType) -> Result<Option<Item>, UseDenied> {
+        if self.equipment_locked {
+            return Err(UseDenied::EquipmentLocked);
+        }
+        Ok(self.equipment.unequip(slot))
+    }
+
+    /// Adds experience to the entity. For every level gained, its stats grow and it
+    /// learns the skills its class teaches at that level.
+    pub fn gain_experience(&mut self, experience: usize) -> LevelUpReport {
+        levels::level_up(
+            self.id,
+            experience,
+            &mut self.level_data,
+            &mut self.stats,
+            self.class.as_ref(),
+            Some(&mut self.skills),
+        )
+    }
+
+    /// Teaches a skill to the entity if it has a free skill slot.
+    pub fn learn_skill(&mut self, skill: Skill) -> SkillEvent {
+        self.skills.learn(skill)
+    }
+
+    /// Forgets the skill with the given name. Returns the skill if it was known.
+    pub fn forget_skill(&mut self, name: &str) -> Option<Skill> {
+        self.skills.forget(name)
+    }
+
+    /// Forgets a skill to make room for another, keeping its slot. Does nothing and
+    /// returns false if the skill to forget is not known, or the new skill already is.
+    pub fn replace_skill(&mut self, forget: &str, skill: Skill) -> bool {
+        self.skills.replace(forget, skill)
+    }
+
+    /// Changes the class of the entity. The level data of the current class is stored,
+    /// under Entity::NO_CLASS if it had none, and the level data the entity last had in
+    /// the new class is restored, starting at level 1 if it never had that class. The
+    /// growth of the new class is applied to the stats, and the equipment the new class
+    /// cannot wear is unequipped and returned, unless the equipment is locked. Nothing
+    /// happens if the entity already has the class.
+    pub fn change_class(&mut self, class: Class) -> Vec<Item> {
+        if self
+            .class
+            .as_ref()
+            .is_some_and(|old| old.name == class.name)
+        {
+            return Vec::new();
+        }
+        let level_data = self
+            .class_levels
+            .remove(class.name.as_ref())
+            .unwrap_or_else(|| LevelData::new(1, 0));
+        let previous = std::mem::replace(&mut self.level_data, level_data);
+        let key = match &self.class {
+            Some(old) => old.name.clone(),
+            None => Cow::Borrowed(Self::NO_CLASS),
+        };
+        self.class_levels.insert(key, previous);
+
+        class.apply_growth(&mut self.stats);
+        let slots = self
+            .equipment
+            .iter()
+            .filter(|item| !class.can_equip(item))
+            .filter_map(Item::slot)
+            .collect::<Vec<_>>();
+        self.class = Some(class);
+        if self.equipment_locked {
+            return Vec::new();
+        }
+        slots
+            .into_iter()
+            .filter_map(|slot| self.equipment.unequip(slot))
+            .collect()
+    }
+}
+
+impl EntityBuilder {
+    /// Gives the entity a class, starting it with the stats of that class.
+    pub fn with_class(self, class: Class) -> Self {
+        self.stats(class.stats()).class(Some(class))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{effects::StatusKind, stats::StatGrowth, testing};
+
+    #[test]
+    fn changing_class_stores_and_restores_levels() {
+        let knight = testing::class("Knight").build().unwrap();
+        let mage = testing::class("Mage").build().unwrap();
+        let mut entity = testing::entity("Hero")
+            .level_data(LevelData::new(7, 0))
+            .build()
+            .unwrap();
+
+        entity.change_class(knight.clone());
+        assert_eq!(entity.level_data.level(), 1);
+        assert_eq!(entity.class_levels[Entity::NO_CLASS].level(), 7);
+
+        entity.level_data = LevelData::new(4, 0);
+        entity.change_class(mage);
+        assert_eq!(entity.level_data.level(), 1);
+        entity.change_class(knight);
+        assert_eq!(entity.level_data.level(), 4);
+        assert!(entity.class_levels.contains_key("Mage"));
+    }
+
+    #[test]
+    fn changing_to_the_same_class_does_nothing() {
+        let knight = testing::class("Knight").build().unwrap();
+        let mut entity = testing::entity("Hero")
+            .level_data(LevelData::new(5, 0))
+            .with_class(knight.clone())
+            .build()
+            .unwrap();
+        assert!(entity.change_class(knight).is_empty());
+        assert_eq!(entity.level_data.level(), 5);
+        assert!(entity.class_levels.is_empty());
+    }
+
+    #[test]
+    fn changing_class_applies_growth_and_unequips_forbidden_items() {
+        let mage = testing::class("Mage")
+            .growth(vec![(StatName::SpecialAttack, StatGrowth::Fast)])
+            .equipment(Some(vec![EquipmentType::Head]))
+            .build()
+            .unwrap();
+        let mut entity = testing::entity("Hero").build().unwrap();
+        let armour = testing::equipment(EquipmentType::Armour, &[]);
+        let armour_id = armour.id;
+        entity.equip(armour).unwrap();
+        entity
+            .equip(testing::equipment(EquipmentType::Head, &[]))
+            .unwrap();
+
+        let removed = entity.change_class(mage);
+        assert_eq!(
+            removed.iter().map(|item| item.id).collect::<Vec<_>>(),
+            [armour_id]
+        );
+        assert!(entity.equipment().get(EquipmentType::Head).is_some());
+        assert_eq!(
+            entity.stats[StatName::SpecialAttack].stat_growth,
+            StatGrowth::Fast
+        );
+    }
+
+    #[test]
+    fn levelling_up_teaches_the_skills_of_the_class() {
+        let fire = Skill::new("Fire", 4, Default::default(), vec![]);
+        let ice = Skill::new("Ice", 4, Default::default(), vec![]);
+        let mage = testing::class("Mage")
+            .learnset(vec![(2, fire), (5, ice)])
+            .build()
+            .unwrap();
+        let mut entity = testing::entity("Hero").with_class(mage).build().unwrap();
+
+        let report = entity.gain_experience(LevelData::experience_for_level(1));
+        assert_eq!(report.levels_gained(), 1);
+        assert_eq!(report.skills, vec![SkillEvent::Learned("Fire".into())]);
+        assert_eq!(entity.skills.len(), 1);
+    }
+
+    #[test]
+    fn skills_beyond_the_slots_must_replace_another() {
+        let mut entity = testing::entity("Hero")
+            .skills(SkillSet::new(1))
+            .build()
+            .unwrap();
+        let fire = Skill::new("Fire", 4, Default::default(), vec![]);
+        let ice = Skill::new("Ice", 4, Default::default(), vec![]);
+        assert_eq!(
+            entity.learn_skill(fire.clone()),
+            SkillEvent::Learned("Fire".into())
+        );
+        assert_eq!(
+            entity.learn_skill(fire),
+            SkillEvent::AlreadyKnown("Fire".into())
+        );
+        assert_eq!(
+            entity.learn_skill(ice.clone()),
+            SkillEvent::MustForget(ice.clone())
+        );
+        assert!(entity.replace_skill("Fire", ice));
+        assert!(entity.skills.get("Ice").is_some());
+        assert!(entity.forget_skill("Fire").is_none());
+    }
+
+    #[test]
+    fn names_can_be_chosen_at_runtime() {
+        let chosen = String::from("  Aria ");
+        let entity = testing::entity("Hero")
+            .name(chosen.trim().to_string())
+            .key(format!("player_{}", 1))
+            .build()
+            .unwrap();
+        assert_eq!(entity.name, "Aria");
+        assert_eq!(entity.name_key().as_deref(), Some("entity.player_1.name"));
+        assert_eq!(testing::entity("Slime").build().unwrap().name_key(), None);
+    }
+
+    #[test]
+    fn running_out_of_health_knocks_out() {
+        let mut entity = testing::entity("Hero").build().unwrap();
+        entity
+            .statuses
+            .add(StatusKind::Poison, 3, &mut entity.stats);
+        entity.stats[StatName::HealthPoints].damage(99);
+        assert!(!entity.check_knock_out());
+
+        entity.stats[StatName::HealthPoints].damage(1);
+        assert!(entity.check_knock_out());
+        assert_eq!(entity.life, LifeState::KnockedOut);
+        assert!(!entity.can_act());
+        assert_eq!(entity.statuses.iter().count(), 0);
+        assert!(!entity.check_knock_out());
+    }
+
+    #[test]
+    fn only_knocked_out_entities_are_revived() {
+        let mut entity = testing::entity("Hero").build().unwrap();
+        assert_eq!(entity.revive(Amount::Flat(50)), None);
+
+        entity.stats[StatName::HealthPoints].damage(100);
+        entity.check_knock_out();
+        assert_eq!(entity.revive(Amount::Percent(0.0)), Some(1));
+        assert!(entity.can_act());
+
+        entity.life = LifeState::Dead;
+        assert_eq!(entity.revive(Amount::Flat(50)), None);
+        assert_eq!(entity.stats[StatName::HealthPoints].base(), 1);
+    }
+
+    #[test]
+    fn entities_without_health_stay_down() {
+        let mut entity = testing::entity("Hero")
+            .stats(testing::stats(&[(StatName::HealthPoints, 0)]))
+            .build()
+            .unwrap();
+        assert!(entity.check_knock_out());
+        assert_eq!(entity.revive(Amount::Percent(100.0)), None);
+        assert_eq!(entity.life, LifeState::KnockedOut);
     }
 }
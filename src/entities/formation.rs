@@ -0,0 +1,42 @@
+use crate::misc::WeaponCategory;
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents the row a party member stands in. Members in the front row deal and
+/// take full melee damage, while members in the back row deal and take less. Ranged
+/// weapons are not affected by rows.
+pub enum Row {
+    #[default]
+    Front,
+    Back,
+}
+
+impl Row {
+    /// How much melee damage is multiplied by when dealt from or to the back row.
+    const BACK_ROW_MELEE: f64 = 0.5;
+
+    /// Get the multiplier for damage dealt from this row with the given weapon.
+    pub fn damage_dealt(&self, weapon: WeaponCategory) -> f64 {
+        match self {
+            Self::Back if !weapon.is_ranged() => Self::BACK_ROW_MELEE,
+            _ => 1.0,
+        }
+    }
+
+    /// Get the multiplier for damage taken in this row from the given weapon.
+    pub fn damage_taken(&self, weapon: WeaponCategory) -> f64 {
+        self.damage_dealt(weapon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_back_row_only_lowers_melee_damage() {
+        assert_eq!(Row::Front.damage_dealt(WeaponCategory::Sword), 1.0);
+        assert_eq!(Row::Back.damage_dealt(WeaponCategory::Sword), 0.5);
+        assert_eq!(Row::Back.damage_taken(WeaponCategory::Spear), 0.5);
+        assert_eq!(Row::Back.damage_dealt(WeaponCategory::Bow), 1.0);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    effects::StatusEffects,
+    stats::{StatName, Stats},
+};
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents whether an entity is still in the fight. Only alive entities can act or
+/// be healed.
+pub enum LifeState {
+    #[default]
+    Alive,
+    /// The HealthPoints of the entity ran out. It can be brought back by Revive effects.
+    KnockedOut,
+    /// The entity is gone for good and nothing can affect it anymore.
+    Dead,
+    /// The entity was turned to stone. Nothing but Unpetrify effects can affect it.
+    Petrified,
+}
+
+impl LifeState {
+    /// Whether the entity is alive.
+    pub fn is_alive(&self) -> bool {
+        *self == Self::Alive
+    }
+
+    /// Whether the entity can take its turn. Statuses such as Sleep can still stop
+    /// an alive entity from acting, see Entity::can_act.
+    pub fn can_act(&self) -> bool {
+        self.is_alive()
+    }
+
+    /// Whether the entity can be brought back by Revive effects.
+    pub fn is_revivable(&self) -> bool {
+        *self == Self::KnockedOut
+    }
+
+    /// Knocks out an alive entity whose HealthPoints have run out, clearing its
+    /// statuses. Returns whether it was knocked out.
+    pub fn check_knock_out(&mut self, statuses: &mut StatusEffects, stats: &mut Stats) -> bool {
+        if !self.is_alive() || !stats[StatName::HealthPoints].is_depleted() {
+            return false;
+        }
+        statuses.clear(stats);
+        *self = Self::KnockedOut;
+        true
+    }
+}
@@ -1,5 +1,19 @@
+mod class;
+mod enemy;
 mod entity;
+mod formation;
+mod lifestate;
 mod party;
+mod partyerror;
+mod partyevent;
+mod partymember;
 
+pub use class::{Class, ClassBuilder};
+pub use enemy::Enemy;
 pub use entity::{Entity, EntityBuilder};
+pub use formation::Row;
+pub use lifestate::LifeState;
 pub use party::Party;
+pub use partyerror::PartyError;
+pub use partyevent::PartyEvent;
+pub use partymember::{MemberFlags, PartyMember};
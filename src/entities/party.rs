@@ -1,71 +1,873 @@
 #![allow(dead_code)]
-use super::Entity;
+use super::{Entity, LifeState, MemberFlags, PartyError, PartyEvent, PartyMember, Row};
+use crate::{
+    ids::EntityId,
+    loot::{Drops, RewardConfig, RewardReport},
+    misc::{EquipmentType, Inventory, Item},
+};
 
 #[derive(Debug, Default)]
 /// Represents the current state of the party. Party members are divided into the
 /// active party and the reserved party. Only active_party members are allowed in
 /// battles, while the reserved party members can be switched with active members
-/// outside of battle.
+/// outside of battle. The order of the active party is the order of its slots, and
+/// one active member is the leader.
 pub struct Party {
     /// The party members who are allowed in battle. MUST be <= 4
-    active_party: Vec<Entity>,
+    active_party: Vec<PartyMember>,
     /// The party members who are `reserved` and do not participate in battles unless
     /// switched out.
-    reserved_party: Vec<Entity>,
+    reserved_party: Vec<PartyMember>,
+    /// The id of the leader. Always an active member that is not a guest if set.
+    leader: Option<EntityId>,
+    /// The gold shared by the whole party.
+    gold: usize,
+    /// The items carried by the party that are not equipped on anyone.
+    inventory: Inventory,
 }
 
 impl Party {
+    /// The most members the active party can have.
+    pub const MAX_ACTIVE: usize = 4;
+
     /// Create a new instance of a Party, given the active party and reserved party.
-    /// Make sure that the active party length is less than or equal to four.
-    pub fn new(active_party: Vec<Entity>, reserved_party: Vec<Entity>) -> Self {
-        if active_party.len() <= 4 {
-            Self {
-                active_party,
-                reserved_party,
-            }
-        } else {
-            panic!("There can only be four active party members.");
+    /// Fails if the active party has more than four members or two members have the
+    /// same id. The first active member becomes the
+    /// leader, and everyone starts in the front row.
+    pub fn new(active_party: Vec<Entity>, reserved_party: Vec<Entity>) -> Result<Self, PartyError> {
+        if active_party.len() > Self::MAX_ACTIVE {
+            return Err(PartyError::TooManyActive);
         }
+        let ids = active_party
+            .iter()
+            .chain(&reserved_party)
+            .map(|e| e.id())
+            .collect::<Vec<_>>();
+        if let Some(index) = (1..ids.len()).find(|&index| ids[..index].contains(&ids[index])) {
+            return Err(PartyError::Duplicate(ids[index]));
+        }
+        Ok(Self {
+            leader: active_party.first().map(|entity| entity.id()),
+            active_party: active_party.into_iter().map(PartyMember::new).collect(),
+            reserved_party: reserved_party.into_iter().map(PartyMember::new).collect(),
+            gold: 0,
+            inventory: Inventory::default(),
+        })
     }
 
     /// Adds a new party member into the active party as long as the active party
     /// is smaller than four.
-    pub fn add_active(&mut self, party_member: Entity) {
-        if self.active_party.len() >= 4 {
-            panic!("There can only be four active party members.");
+    pub fn add_active(&mut self, party_member: Entity) -> Result<(), PartyError> {
+        self.check_new(party_member.id())?;
+        if self.active_party.len() >= Self::MAX_ACTIVE {
+            return Err(PartyError::TooManyActive);
+        }
+        self.leader.get_or_insert(party_member.id());
+        self.active_party.push(PartyMember::new(party_member));
+        Ok(())
+    }
+
+    /// Adds a new party member into the reserved party. Fails if a party member
+    /// already has the same id.
+    pub fn add_reserved(&mut self, party_member: Entity) -> Result<(), PartyError> {
+        self.check_new(party_member.id())?;
+        self.reserved_party.push(PartyMember::new(party_member));
+        Ok(())
+    }
+
+    /// Adds a guest to the active party, who fights alongside the party until it
+    /// leaves through remove_guest.
+    pub fn add_guest(&mut self, guest: Entity) -> Result<PartyEvent, PartyError> {
+        self.check_new(guest.id())?;
+        if self.active_party.len() >= Self::MAX_ACTIVE {
+            return Err(PartyError::TooManyActive);
+        }
+        let id = guest.id();
+        let mut guest = guest;
+        guest.set_equipment_locked(true);
+        self.active_party.push(PartyMember {
+            flags: MemberFlags::guest(),
+            ..PartyMember::new(guest)
+        });
+        self.fix_leader();
+        Ok(PartyEvent::GuestJoined(id))
+    }
+
+    /// Removes a guest from the party, returning it along with the event.
+    pub fn remove_guest(&mut self, id: EntityId) -> Result<(Entity, PartyEvent), PartyError> {
+        let index = self.active_index(id)?;
+        if !self.active_party[index].flags.guest {
+            return Err(PartyError::NotGuest(id));
         }
-        self.active_party.push(party_member);
+        let mut member = self.active_party.remove(index);
+        member.entity.set_equipment_locked(false);
+        self.fix_leader();
+        Ok((member.entity, PartyEvent::GuestLeft(id)))
     }
 
-    /// Adds a new party member into the reserved party.
-    pub fn add_reserved(&mut self, party_member: Entity) {
-        self.reserved_party.push(party_member);
+    /// Removes the party member with the given id from whichever party it is in.
+    /// Locked and permanent members cannot be removed, and guests must be removed
+    /// through remove_guest.
+    pub fn remove(&mut self, id: EntityId) -> Result<Entity, PartyError> {
+        match self.get(id).map(|member| member.flags) {
+            None => return Err(PartyError::NotFound(id)),
+            Some(flags) if flags.guest => return Err(PartyError::Guest(id)),
+            Some(flags) if flags.locked => return Err(PartyError::Locked(id)),
+            Some(flags) if flags.permanent => return Err(PartyError::Permanent(id)),
+            Some(_) => {}
+        }
+
+        if let Ok(index) = self.active_index(id) {
+            let member = self.active_party.remove(index);
+            self.fix_leader();
+            Ok(member.entity)
+        } else {
+            let index = self.reserved_index(id)?;
+            Ok(self.reserved_party.remove(index).entity)
+        }
+    }
+
+    /// Get the party member with the given id, from either party.
+    pub fn get(&self, id: EntityId) -> Option<&PartyMember> {
+        self.members().find(|member| member.entity.id() == id)
+    }
+
+    /// Get the entity with the given id, from either party.
+    pub fn entity(&self, id: EntityId) -> Option<&Entity> {
+        self.get(id).map(|member| &member.entity)
+    }
+
+    /// Changes the party member with the given id through the closure, returning what
+    /// the closure returns. The member keeps its id, and guests keep their equipment,
+    /// even if the closure replaces the whole entity.
+    pub fn modify<R>(
+        &mut self,
+        id: EntityId,
+        f: impl FnOnce(&mut Entity) -> R,
+    ) -> Result<R, PartyError> {
+        match self.get_mut(id) {
+            Some(member) => Ok(member.entity.modify(f)),
+            None => Err(PartyError::NotFound(id)),
+        }
+    }
+
+    /// Changes every party member through the closure, such as to heal everyone at an
+    /// inn. Members keep their ids and guests keep their equipment, as with modify.
+    pub fn modify_all(&mut self, mut f: impl FnMut(&mut Entity)) {
+        self.active_party
+            .iter_mut()
+            .chain(&mut self.reserved_party)
+            .for_each(|member| member.entity.modify(&mut f));
+    }
+
+    /// Whether the party member with the given id is in the active party.
+    pub fn is_active(&self, id: EntityId) -> bool {
+        self.active_index(id).is_ok()
+    }
+
+    /// Iterates over the active party in slot order.
+    pub fn active(&self) -> std::slice::Iter<'_, PartyMember> {
+        self.active_party.iter()
+    }
+
+    /// Iterates over the reserved party.
+    pub fn reserved(&self) -> std::slice::Iter<'_, PartyMember> {
+        self.reserved_party.iter()
+    }
+
+    /// Iterates over every party member, active members first.
+    pub fn members(&self) -> impl Iterator<Item = &PartyMember> {
+        self.active_party.iter().chain(&self.reserved_party)
+    }
+
+    /// Iterates over the active members that are able to take their turn.
+    pub fn able(&self) -> impl Iterator<Item = &PartyMember> {
+        self.active_party
+            .iter()
+            .filter(|member| member.entity.can_act())
+    }
+
+    /// Iterates over the active members that are knocked out.
+    pub fn knocked_out(&self) -> impl Iterator<Item = &PartyMember> {
+        self.active_party
+            .iter()
+            .filter(|member| member.entity.life == LifeState::KnockedOut)
+    }
+
+    /// Whether no active member is alive, which means the battle is lost.
+    pub fn is_wiped_out(&self) -> bool {
+        !self
+            .active_party
+            .iter()
+            .any(|member| member.entity.life.is_alive())
+    }
+
+    /// Switches an active party member with a reserved party member. The reserved
+    /// member takes the slot of the active member. Nothing changes if either is not
+    /// where it should be or the active member cannot leave the active party.
+    pub fn swap(&mut self, active_id: EntityId, reserved_id: EntityId) -> Result<(), PartyError> {
+        let active = self.active_index(active_id)?;
+        let reserved = self.reserved_index(reserved_id)?;
+        self.check_can_leave(active_id)?;
+
+        std::mem::swap(
+            &mut self.active_party[active],
+            &mut self.reserved_party[reserved],
+        );
+        self.fix_leader();
+        Ok(())
+    }
+
+    /// Moves a reserved member into the last slot of the active party.
+    pub fn promote(&mut self, id: EntityId) -> Result<(), PartyError> {
+        let index = self.reserved_index(id)?;
+        if self.active_party.len() >= Self::MAX_ACTIVE {
+            return Err(PartyError::TooManyActive);
+        }
+        let member = self.reserved_party.remove(index);
+        self.active_party.push(member);
+        self.fix_leader();
+        Ok(())
+    }
+
+    /// Moves an active member into the reserved party. Locked members and guests
+    /// cannot be demoted.
+    pub fn demote(&mut self, id: EntityId) -> Result<(), PartyError> {
+        let index = self.active_index(id)?;
+        self.check_can_leave(id)?;
+        let member = self.active_party.remove(index);
+        self.reserved_party.push(member);
+        self.fix_leader();
+        Ok(())
+    }
+
+    /// Reorders the active party. The ids must be exactly the active members, in their
+    /// new slot order.
+    pub fn reorder(&mut self, ids: &[EntityId]) -> Result<(), PartyError> {
+        for (index, &id) in ids.iter().enumerate() {
+            self.active_index(id)?;
+            if ids[..index].contains(&id) {
+                return Err(PartyError::Duplicate(id));
+            }
+        }
+        if let Some(missing) = self
+            .active_party
+            .iter()
+            .find(|m| !ids.contains(&m.entity.id()))
+        {
+            return Err(PartyError::Missing(missing.entity.id()));
+        }
+
+        self.active_party
+            .sort_by_key(|member| ids.iter().position(|&id| id == member.entity.id()));
+        Ok(())
+    }
+
+    /// Locks an active member into the active party, as the story sometimes demands.
+    /// Locked members cannot be demoted, swapped out or removed.
+    pub fn lock(&mut self, id: EntityId) -> Result<(), PartyError> {
+        let index = self.active_index(id)?;
+        self.active_party[index].flags.locked = true;
+        Ok(())
+    }
+
+    /// Unlocks a locked member.
+    pub fn unlock(&mut self, id: EntityId) -> Result<(), PartyError> {
+        match self.get_mut(id) {
+            Some(member) => {
+                member.flags.locked = false;
+                Ok(())
+            }
+            None => Err(PartyError::NotFound(id)),
+        }
+    }
+
+    /// Sets the flags of the party member with the given id. Guests cannot be made
+    /// through this, use add_guest instead.
+    pub fn set_flags(&mut self, id: EntityId, flags: MemberFlags) -> Result<(), PartyError> {
+        match self.get_mut(id) {
+            Some(member) if member.flags.guest != flags.guest => Err(PartyError::Guest(id)),
+            Some(member) => {
+                member.flags = flags;
+                Ok(())
+            }
+            None => Err(PartyError::NotFound(id)),
+        }
+    }
+
+    /// Equips an item onto the party member with the given id, returning the item
+    /// previously in that slot. Guests cannot be re-equipped.
+    pub fn equip(&mut self, id: EntityId, item: Item) -> Result<Option<Item>, PartyError> {
+        let entity = &mut self.editable(id)?.entity;
+        entity.equip(item).map_err(PartyError::Denied)
+    }
+
+    /// Unequips the item in the given slot of the party member with the given id.
+    /// Guests cannot be re-equipped.
+    pub fn unequip(
+        &mut self,
+        id: EntityId,
+        slot: EquipmentType,
+    ) -> Result<Option<Item>, PartyError> {
+        let entity = &mut self.editable(id)?.entity;
+        entity.unequip(slot).map_err(PartyError::Denied)
+    }
+
+    /// Get the id of the leader of the party.
+    pub fn leader(&self) -> Option<EntityId> {
+        self.leader
+    }
+
+    /// Makes the active member with the given id the leader. Guests cannot lead.
+    pub fn set_leader(&mut self, id: EntityId) -> Result<(), PartyError> {
+        let index = self.active_index(id)?;
+        if self.active_party[index].flags.guest {
+            return Err(PartyError::Guest(id));
+        }
+        self.leader = Some(id);
+        Ok(())
+    }
+
+    /// Get the row of the party member with the given id.
+    pub fn row(&self, id: EntityId) -> Option<Row> {
+        self.get(id).map(|member| member.row)
+    }
+
+    /// Moves the party member with the given id into a row.
+    pub fn set_row(&mut self, id: EntityId, row: Row) -> Result<(), PartyError> {
+        match self.get_mut(id) {
+            Some(member) => {
+                member.row = row;
+                Ok(())
+            }
+            None => Err(PartyError::NotFound(id)),
+        }
+    }
+
+    /// Moves the active member with the given id into another slot, shifting the
+    /// members in between.
+    pub fn set_slot(&mut self, id: EntityId, slot: usize) -> Result<(), PartyError> {
+        let index = self.active_index(id)?;
+        let member = self.active_party.remove(index);
+        let slot = slot.min(self.active_party.len());
+        self.active_party.insert(slot, member);
+        Ok(())
+    }
+
+    /// Replaces the whole formation of the active party. The ids are the new active
+    /// members in slot order, each with its row, and can be taken from either party.
+    /// Active members that are not listed are moved to the reserved party. Nothing
+    /// changes if the formation has more than four members, lists a member twice,
+    /// lists an id that is not in the party or leaves out a locked member or a guest.
+    pub fn set_formation(&mut self, formation: &[(EntityId, Row)]) -> Result<(), PartyError> {
+        if formation.len() > Self::MAX_ACTIVE {
+            return Err(PartyError::TooManyActive);
+        }
+        for (index, &(id, _)) in formation.iter().enumerate() {
+            if formation[..index].iter().any(|&(other, _)| other == id) {
+                return Err(PartyError::Duplicate(id));
+            } else if self.get(id).is_none() {
+                return Err(PartyError::NotFound(id));
+            }
+        }
+        for member in &self.active_party {
+            if !formation.iter().any(|&(id, _)| id == member.entity.id()) {
+                self.check_can_leave(member.entity.id())?;
+            }
+        }
+
+        let mut members = self.active_party.drain(..).collect::<Vec<_>>();
+        members.append(&mut self.reserved_party);
+        for &(id, row) in formation {
+            let index = members.iter().position(|m| m.entity.id() == id).unwrap();
+            let mut member = members.remove(index);
+            member.row = row;
+            self.active_party.push(member);
+        }
+        self.reserved_party = members;
+        self.fix_leader();
+        Ok(())
     }
 
-    /// Removes the party member from a party given its id. Silently fails
-    /// if there are no party members with that id.
-    pub fn remove_by_id(&mut self, party: Vec<Entity>, id: usize) -> Vec<Entity> {
+    /// Get the gold shared by the whole party.
+    pub fn gold(&self) -> usize {
+        self.gold
+    }
+
+    /// Adds gold to the party.
+    pub fn add_gold(&mut self, amount: usize) {
+        self.gold = self.gold.saturating_add(amount);
+    }
+
+    /// Takes gold from the party. Nothing is taken and false is returned if the party
+    /// does not have enough.
+    pub fn spend_gold(&mut self, amount: usize) -> bool {
+        match self.gold.checked_sub(amount) {
+            Some(gold) => {
+                self.gold = gold;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the items carried by the party.
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// Get a mutable reference to the items carried by the party.
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// Hands out the rewards of a battle. The gold goes to the party, and every member
+    /// gains experience according to the config: the full amount for alive active
+    /// members, a share for knocked out active members and for reserved members, and a
+    /// bonus for the ids in final_blows. Dead and petrified members gain nothing and
+    /// are left out of the report. Members level up automatically.
+    pub fn distribute_rewards(
+        &mut self,
+        drops: Drops,
+        config: &RewardConfig,
+        final_blows: &[EntityId],
+    ) -> RewardReport {
+        let share = |entity: &Entity, share: f64| {
+            let bonus = if final_blows.contains(&entity.id()) {
+                config.final_blow_bonus
+            } else {
+                0.0
+            };
+            (drops.experience as f64 * (share + bonus)).floor() as usize
+        };
+
+        let mut level_ups = Vec::new();
+        for PartyMember { entity, .. } in &mut self.active_party {
+            let experience = match entity.life {
+                LifeState::Alive => share(entity, 1.0),
+                LifeState::KnockedOut => share(entity, config.knocked_out_share),
+                LifeState::Dead | LifeState::Petrified => continue,
+            };
+            level_ups.push(entity.gain_experience(experience));
+        }
+        for PartyMember { entity, .. } in &mut self.reserved_party {
+            let experience = match entity.life {
+                LifeState::Dead | LifeState::Petrified => continue,
+                _ => share(entity, config.reserved_share),
+            };
+            level_ups.push(entity.gain_experience(experience));
+        }
+
+        self.gold = self.gold.saturating_add(drops.gold);
+        RewardReport {
+            gold: drops.gold,
+            items: drops.items,
+            level_ups,
+        }
+    }
+
+    /// Get the slot of the active member with the given id.
+    fn active_index(&self, id: EntityId) -> Result<usize, PartyError> {
+        self.active_party
+            .iter()
+            .position(|member| member.entity.id() == id)
+            .ok_or(PartyError::NotActive(id))
+    }
+
+    /// Get a mutable reference to the party member with the given id, from either party.
+    fn get_mut(&mut self, id: EntityId) -> Option<&mut PartyMember> {
+        self.active_party
+            .iter_mut()
+            .chain(&mut self.reserved_party)
+            .find(|member| member.entity.id() == id)
+    }
+
+    /// Get a mutable reference to the party member with the given id, as long as it is
+    /// not a guest.
+    fn editable(&mut self, id: EntityId) -> Result<&mut PartyMember, PartyError> {
+        match self.get_mut(id) {
+            Some(member) if member.flags.guest => Err(PartyError::Guest(id)),
+            Some(member) => Ok(member),
+            None => Err(PartyError::NotFound(id)),
+        }
+    }
+
+    /// Get the index of the reserved member with the given id.
+    fn reserved_index(&self, id: EntityId) -> Result<usize, PartyError> {
+        self.reserved_party
+            .iter()
+            .position(|member| member.entity.id() == id)
+            .ok_or(PartyError::NotFound(id))
+    }
+
+    /// Fails if the id is already taken by a party member.
+    fn check_new(&self, id: EntityId) -> Result<(), PartyError> {
+        match self.get(id) {
+            Some(_) => Err(PartyError::Duplicate(id)),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if the party member with the given id cannot leave the active party.
+    fn check_can_leave(&self, id: EntityId) -> Result<(), PartyError> {
+        match self.get(id) {
+            Some(member) if member.flags.guest => Err(PartyError::Guest(id)),
+            Some(member) if member.flags.locked => Err(PartyError::Locked(id)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Makes the first active member that is not a guest the leader if the leader is
+    /// no longer active. There is no leader if every active member is a guest.
+    fn fix_leader(&mut self) {
+        if self.leader.is_none_or(|id| self.active_index(id).is_err()) {
+            self.leader = self
+                .active_party
+                .iter()
+                .find(|member| !member.flags.guest)
+                .map(|member| member.entity.id());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::UseDenied, stats::StatName, testing};
+
+    fn member(name: &'static str) -> Entity {
+        testing::entity(name).build().unwrap()
+    }
+
+    fn knock_out(party: &mut Party, id: EntityId) {
+        party
+            .modify(id, |entity| {
+                entity.stats[StatName::HealthPoints].damage(100);
+                entity.check_knock_out();
+            })
+            .unwrap();
+    }
+
+    fn experience(party: &Party, id: EntityId) -> usize {
+        party.entity(id).unwrap().level_data.current_experience()
+    }
+
+    #[test]
+    fn rewards_are_shared_by_the_config() {
+        let (hero, fallen, resting) = (member("Hero"), member("Fallen"), member("Resting"));
+        let ids = (hero.id(), fallen.id(), resting.id());
+        let mut party = Party::new(vec![hero, fallen], vec![resting]).unwrap();
+        party
+            .modify(ids.1, |entity| entity.life = LifeState::KnockedOut)
+            .unwrap();
+
+        let drops = Drops {
+            items: vec![],
+            gold: 50,
+            experience: 100,
+        };
+        let report = party.distribute_rewards(drops, &RewardConfig::default(), &[ids.0]);
+        assert_eq!(report.gold, 50);
+        assert_eq!(party.gold(), 50);
+        assert_eq!(report.level_ups.len(), 3);
+        assert_eq!(experience(&party, ids.0), 110);
+        assert_eq!(experience(&party, ids.1), 0);
+        assert_eq!(experience(&party, ids.2), 50);
+    }
+
+    #[test]
+    fn knocked_out_members_stay_down_after_levelling_up() {
+        let fallen = member("Fallen");
+        let id = fallen.id();
+        let mut party = Party::new(vec![fallen], vec![]).unwrap();
+        knock_out(&mut party, id);
+
+        let config = RewardConfig {
+            knocked_out_share: 1.0,
+            ..RewardConfig::default()
+        };
+        let drops = Drops {
+            experience: 5000,
+            ..Drops::default()
+        };
+        let report = party.distribute_rewards(drops, &config, &[]);
+        assert!(report.level_ups[0].levels_gained() > 0);
+        let entity = party.entity(id).unwrap();
+        assert_eq!(entity.life, LifeState::KnockedOut);
+        assert!(entity.stats[StatName::HealthPoints].is_depleted());
+    }
+
+    #[test]
+    fn the_first_active_member_leads() {
+        let (hero, second, resting) = (member("Hero"), member("Second"), member("Resting"));
+        let ids = (hero.id(), second.id(), resting.id());
+        let mut party = Party::new(vec![hero, second], vec![resting]).unwrap();
+        assert_eq!(party.leader(), Some(ids.0));
+        assert_eq!(party.set_leader(ids.2), Err(PartyError::NotActive(ids.2)));
+        party.set_leader(ids.1).unwrap();
+        party.demote(ids.1).unwrap();
+        assert_eq!(party.leader(), Some(ids.0));
+    }
+
+    #[test]
+    fn rows_and_slots_can_be_set() {
+        let (hero, second) = (member("Hero"), member("Second"));
+        let ids = (hero.id(), second.id());
+        let mut party = Party::new(vec![hero, second], vec![]).unwrap();
+        assert_eq!(party.row(ids.0), Some(Row::Front));
+        party.set_row(ids.0, Row::Back).unwrap();
+        assert_eq!(party.get(ids.0).map(PartyMember::row), Some(Row::Back));
+        party.set_slot(ids.1, 0).unwrap();
+        let order = party.active().map(|m| m.entity().id()).collect::<Vec<_>>();
+        assert_eq!(order, [ids.1, ids.0]);
+    }
+
+    fn order(party: &Party) -> Vec<EntityId> {
+        party.active().map(|m| m.entity().id()).collect()
+    }
+
+    #[test]
+    fn members_move_between_the_parties() {
+        let names = ["A", "B", "C", "D", "E"];
+        let members = names.iter().map(|&name| member(name)).collect::<Vec<_>>();
+        let ids = members.iter().map(Entity::id).collect::<Vec<_>>();
+        let mut members = members.into_iter();
+        let active = members.by_ref().take(4).collect();
+        let mut party = Party::new(active, members.collect()).unwrap();
+
+        assert_eq!(party.promote(ids[4]), Err(PartyError::TooManyActive));
+        party.swap(ids[1], ids[4]).unwrap();
+        assert_eq!(order(&party), [ids[0], ids[4], ids[2], ids[3]]);
+        assert!(!party.is_active(ids[1]));
+
+        party.demote(ids[0]).unwrap();
+        assert_eq!(party.leader(), Some(ids[4]));
+        party.promote(ids[1]).unwrap();
+        assert_eq!(order(&party), [ids[4], ids[2], ids[3], ids[1]]);
+    }
+
+    #[test]
+    fn reorder_needs_every_active_member_once() {
+        let (a, b, c) = (member("A"), member("B"), member("C"));
+        let ids = (a.id(), b.id(), c.id());
+        let mut party = Party::new(vec![a, b], vec![c]).unwrap();
+        assert_eq!(party.reorder(&[ids.0]), Err(PartyError::Missing(ids.1)));
+        assert_eq!(
+            party.reorder(&[ids.0, ids.0]),
+            Err(PartyError::Duplicate(ids.0))
+        );
+        assert_eq!(
+            party.reorder(&[ids.0, ids.2]),
+            Err(PartyError::NotActive(ids.2))
+        );
+        assert_eq!(order(&party), [ids.0, ids.1]);
+        party.reorder(&[ids.1, ids.0]).unwrap();
+        assert_eq!(order(&party), [ids.1, ids.0]);
+    }
+
+    #[test]
+    fn locked_members_cannot_leave() {
+        let (a, b) = (member("A"), member("B"));
+        let ids = (a.id(), b.id());
+        let mut party = Party::new(vec![a], vec![b]).unwrap();
+        party.lock(ids.0).unwrap();
+        assert_eq!(party.demote(ids.0), Err(PartyError::Locked(ids.0)));
+        assert_eq!(party.swap(ids.0, ids.1), Err(PartyError::Locked(ids.0)));
+        assert_eq!(party.remove(ids.0).err(), Some(PartyError::Locked(ids.0)));
+        assert_eq!(
+            party.set_formation(&[(ids.1, Row::Front)]),
+            Err(PartyError::Locked(ids.0))
+        );
+        party.unlock(ids.0).unwrap();
+        party.demote(ids.0).unwrap();
+    }
+
+    #[test]
+    fn formations_replace_the_active_party() {
+        let (a, b, c) = (member("A"), member("B"), member("C"));
+        let ids = (a.id(), b.id(), c.id());
+        let mut party = Party::new(vec![a, b], vec![c]).unwrap();
+        assert_eq!(
+            party.set_formation(&[(ids.2, Row::Back), (ids.2, Row::Front)]),
+            Err(PartyError::Duplicate(ids.2))
+        );
         party
-            .into_iter()
-            .filter(|entity| entity.id != id)
-            .collect::<Vec<Entity>>()
+            .set_formation(&[(ids.2, Row::Back), (ids.1, Row::Front)])
+            .unwrap();
+        assert_eq!(order(&party), [ids.2, ids.1]);
+        assert_eq!(party.row(ids.2), Some(Row::Back));
+        assert_eq!(party.leader(), Some(ids.2));
+        assert!(party.reserved().any(|m| m.entity().id() == ids.0));
+    }
+
+    #[test]
+    fn guests_keep_their_own_equipment() {
+        let (hero, guest) = (member("Hero"), member("Guest"));
+        let ids = (hero.id(), guest.id());
+        let mut party = Party::new(vec![hero], vec![]).unwrap();
+        assert_eq!(party.add_guest(guest), Ok(PartyEvent::GuestJoined(ids.1)));
+        assert!(party.get(ids.1).unwrap().flags().ai_controlled);
+
+        let sword = testing::equipment(EquipmentType::Weapon, &[]);
+        assert_eq!(
+            party.equip(ids.1, sword.clone()),
+            Err(PartyError::Guest(ids.1))
+        );
+        assert_eq!(
+            party.unequip(ids.1, EquipmentType::Weapon),
+            Err(PartyError::Guest(ids.1))
+        );
+        assert_eq!(
+            party.modify(ids.1, |entity| entity.equip(sword.clone())),
+            Ok(Err(UseDenied::EquipmentLocked))
+        );
+        assert!(party.equip(ids.0, sword.clone()).is_ok());
+
+        let (mut guest, event) = party.remove_guest(ids.1).unwrap();
+        assert_eq!(event, PartyEvent::GuestLeft(ids.1));
+        assert!(!guest.equipment_locked());
+        assert!(guest.equip(sword).is_ok());
+    }
+
+    #[test]
+    fn guests_never_lead() {
+        let (guest, hero) = (member("Guest"), member("Hero"));
+        let ids = (guest.id(), hero.id());
+        let mut party = Party::new(vec![], vec![]).unwrap();
+        party.add_guest(guest).unwrap();
+        assert_eq!(party.leader(), None);
+        assert_eq!(party.set_leader(ids.0), Err(PartyError::Guest(ids.0)));
+        party.add_active(hero).unwrap();
+        assert_eq!(party.leader(), Some(ids.1));
+        party.set_slot(ids.1, 0).unwrap();
+        assert_eq!(party.leader(), Some(ids.1));
+    }
+
+    #[test]
+    fn guests_only_come_and_go_as_guests() {
+        let (hero, guest) = (member("Hero"), member("Guest"));
+        let ids = (hero.id(), guest.id());
+        let mut party = Party::new(vec![hero], vec![]).unwrap();
+        party.add_guest(guest).unwrap();
+        assert_eq!(
+            party.set_flags(ids.0, MemberFlags::guest()),
+            Err(PartyError::Guest(ids.0))
+        );
+        assert_eq!(
+            party.set_flags(ids.1, MemberFlags::default()),
+            Err(PartyError::Guest(ids.1))
+        );
+        assert_eq!(party.remove(ids.1).err(), Some(PartyError::Guest(ids.1)));
+        assert_eq!(party.demote(ids.1), Err(PartyError::Guest(ids.1)));
+        assert_eq!(
+            party.remove_guest(ids.0).err(),
+            Some(PartyError::NotGuest(ids.0))
+        );
+    }
+
+    #[test]
+    fn new_parties_are_checked() {
+        let hero = member("Hero");
+        let id = hero.id();
+        assert_eq!(
+            Party::new(vec![hero.clone()], vec![hero.clone()]).err(),
+            Some(PartyError::Duplicate(id))
+        );
+        let crowd = (0..5).map(|_| hero.instantiate()).collect();
+        assert_eq!(
+            Party::new(crowd, vec![]).err(),
+            Some(PartyError::TooManyActive)
+        );
+
+        let mut party = Party::new(vec![hero.clone()], vec![]).unwrap();
+        assert_eq!(party.add_reserved(hero), Err(PartyError::Duplicate(id)));
+    }
+
+    #[test]
+    fn the_party_is_wiped_out_when_no_active_member_is_alive() {
+        let (first, second, resting) = (member("First"), member("Second"), member("Resting"));
+        let ids = (first.id(), second.id());
+        let mut party = Party::new(vec![first, second], vec![resting]).unwrap();
+        knock_out(&mut party, ids.0);
+        assert!(!party.is_wiped_out());
+        let able = party.able().map(|m| m.entity().id()).collect::<Vec<_>>();
+        assert_eq!(able, [ids.1]);
+        assert_eq!(party.knocked_out().count(), 1);
+
+        knock_out(&mut party, ids.1);
+        assert!(party.is_wiped_out());
+        assert_eq!(party.able().count(), 0);
     }
 
-    /// Removes the party member from the active party.
-    pub fn remove_active(&mut self, entity: Entity) {
-        self.active_party = self.remove_by_id(self.active_party.clone(), entity.id);
+    #[test]
+    fn replacing_a_member_keeps_its_id() {
+        let (hero, other) = (member("Hero"), member("Other"));
+        let ids = (hero.id(), other.id());
+        let mut party = Party::new(vec![hero, other.clone()], vec![]).unwrap();
+        party.modify(ids.0, |entity| *entity = other).unwrap();
+        assert_eq!(party.entity(ids.0).unwrap().name, "Other");
+        assert_eq!(order(&party), [ids.0, ids.1]);
+        assert_eq!(
+            party.modify(EntityId(0), |_| ()),
+            Err(PartyError::NotFound(EntityId(0)))
+        );
     }
 
-    /// Removes the party member from the reserved party.
-    pub fn remove_reserved(&mut self, entity: Entity) {
-        self.reserved_party = self.remove_by_id(self.reserved_party.clone(), entity.id);
+    #[test]
+    fn replacing_a_guest_keeps_its_equipment_locked() {
+        let guest = member("Guest");
+        let id = guest.id();
+        let mut party = Party::new(vec![], vec![]).unwrap();
+        party.add_guest(guest.clone()).unwrap();
+
+        let sword = testing::equipment(EquipmentType::Weapon, &[]);
+        party
+            .modify(id, |entity| {
+                *entity = guest;
+                let _ = entity.equip(sword);
+            })
+            .unwrap();
+        let entity = party.entity(id).unwrap();
+        assert!(entity.equipment_locked());
+        assert!(entity.equipment().get(EquipmentType::Weapon).is_none());
     }
 
-    /// Switches an active party member with a reserved party member.
-    pub fn switch_members(&mut self, active_member: Entity, reserved_member: Entity) {
-        self.remove_active(active_member.clone());
-        self.add_reserved(active_member);
-        self.add_active(reserved_member.clone());
-        self.remove_reserved(reserved_member);
+    #[test]
+    fn every_member_can_be_changed_at_once() {
+        let (hero, resting) = (member("Hero"), member("Resting"));
+        let mut party = Party::new(vec![hero], vec![resting]).unwrap();
+        party.modify_all(|entity| {
+            entity.stats[StatName::HealthPoints].damage(10);
+        });
+        assert!(party
+            .members()
+            .all(|m| m.entity().stats[StatName::HealthPoints].base() == 90));
+    }
+
+    #[test]
+    fn dead_and_petrified_members_gain_nothing() {
+        let (hero, dead, stone) = (member("Hero"), member("Dead"), member("Stone"));
+        let ids = (hero.id(), dead.id(), stone.id());
+        let mut party = Party::new(vec![hero, dead], vec![stone]).unwrap();
+        party
+            .modify(ids.1, |entity| entity.life = LifeState::Dead)
+            .unwrap();
+        party
+            .modify(ids.2, |entity| entity.life = LifeState::Petrified)
+            .unwrap();
+
+        let config = RewardConfig {
+            knocked_out_share: 1.0,
+            ..RewardConfig::default()
+        };
+        let drops = Drops {
+            experience: 100,
+            ..Drops::default()
+        };
+        let report = party.distribute_rewards(drops, &config, &[ids.1]);
+        assert_eq!(report.level_ups.len(), 1);
+        assert_eq!(experience(&party, ids.0), 100);
+        assert_eq!(experience(&party, ids.1), 0);
+        assert_eq!(experience(&party, ids.2), 0);
     }
 }
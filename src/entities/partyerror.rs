@@ -0,0 +1,47 @@
+use crate::{ids::EntityId, misc::UseDenied};
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+/// Represents the reasons a change to the party can be rejected. A rejected change
+/// leaves the party as it was.
+pub enum PartyError {
+    /// The change would put more than four members in the active party.
+    TooManyActive,
+    /// There is no party member with this id.
+    NotFound(EntityId),
+    /// The party member with this id was listed more than once.
+    Duplicate(EntityId),
+    /// The party member with this id is not in the active party.
+    NotActive(EntityId),
+    /// The active member with this id was left out of a new order of the active party.
+    Missing(EntityId),
+    /// The party member with this id is locked into the active party.
+    Locked(EntityId),
+    /// The party member with this id cannot be removed from the party.
+    Permanent(EntityId),
+    /// The party member with this id is a guest, who cannot be changed this way.
+    Guest(EntityId),
+    /// The party member with this id is not a guest.
+    NotGuest(EntityId),
+    /// The party member is not allowed to equip the item.
+    Denied(UseDenied),
+}
+
+impl fmt::Display for PartyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyActive => write!(f, "There can only be four active party members."),
+            Self::NotFound(id) => write!(f, "There is no party member with id {}", id),
+            Self::Duplicate(id) => write!(f, "Party member {} was listed twice", id),
+            Self::NotActive(id) => write!(f, "Party member {} is not active", id),
+            Self::Missing(id) => write!(f, "Party member {} was left out", id),
+            Self::Locked(id) => write!(f, "Party member {} cannot leave the active party", id),
+            Self::Permanent(id) => write!(f, "Party member {} cannot be removed", id),
+            Self::Guest(id) => write!(f, "Party member {} is a guest", id),
+            Self::NotGuest(id) => write!(f, "Party member {} is not a guest", id),
+            Self::Denied(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for PartyError {}
@@ -0,0 +1,10 @@
+use crate::ids::EntityId;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents a change to the party that the game may want to announce.
+pub enum PartyEvent {
+    /// The guest with this id joined the party.
+    GuestJoined(EntityId),
+    /// The guest with this id left the party.
+    GuestLeft(EntityId),
+}
@@ -0,0 +1,66 @@
+use super::{Entity, Row};
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents the story restrictions placed on a party member. Party operations and
+/// the equipment APIs on Party honor these.
+pub struct MemberFlags {
+    /// A temporary member that fights alongside the party. Guests stay in the active
+    /// party, cannot be re-equipped, and only leave through Party::remove_guest.
+    pub guest: bool,
+    /// The member cannot leave the active party.
+    pub locked: bool,
+    /// The member cannot be removed from the party.
+    pub permanent: bool,
+    /// The member acts on its own in battle instead of taking orders from the player.
+    pub ai_controlled: bool,
+}
+
+impl MemberFlags {
+    /// The flags of a guest, who is controlled by the AI.
+    pub fn guest() -> Self {
+        Self {
+            guest: true,
+            ai_controlled: true,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an entity that is a member of the party, along with its place in the
+/// formation. Reserved members keep their row for when they are switched in. The
+/// row and flags are changed through Party so its rules are kept.
+pub struct PartyMember {
+    /// The member itself.
+    pub(crate) entity: Entity,
+    /// The row the member stands in while active.
+    pub(crate) row: Row,
+    /// The story restrictions placed on the member.
+    pub(crate) flags: MemberFlags,
+}
+
+impl PartyMember {
+    /// Create a new party member standing in the front row without any flags.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            row: Row::default(),
+            flags: MemberFlags::default(),
+        }
+    }
+
+    /// Get the member itself.
+    pub fn entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    /// Get the row the member stands in while active.
+    pub fn row(&self) -> Row {
+        self.row
+    }
+
+    /// Get the story restrictions placed on the member.
+    pub fn flags(&self) -> MemberFlags {
+        self.flags
+    }
+}
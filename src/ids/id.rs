@@ -0,0 +1,92 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The next id handed out by EntityId::generate.
+static NEXT_ENTITY: AtomicUsize = AtomicUsize::new(1);
+/// The next id handed out by ItemId::generate.
+static NEXT_ITEM: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Identifies a single entity. Every entity should have its own id, which is what
+/// the party looks members up by.
+pub struct EntityId(pub usize);
+
+impl EntityId {
+    /// Get a new id that no other call to this function has returned. Ids set by hand
+    /// are not tracked, so they may collide with these unless they are reserved.
+    pub fn generate() -> Self {
+        Self(NEXT_ENTITY.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Makes sure generate never returns the given id or any id below it. Call this
+    /// with the highest id in a save file once it is loaded.
+    pub fn reserve_through(last: Self) {
+        NEXT_ENTITY.fetch_max(last.0.saturating_add(1), Ordering::Relaxed);
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Identifies a single instance of an item. Two potions in the inventory have
+/// different item ids but the same template id.
+pub struct ItemId(pub usize);
+
+impl ItemId {
+    /// Get a new id that no other call to this function has returned. Ids set by hand
+    /// are not tracked, so they may collide with these unless they are reserved.
+    pub fn generate() -> Self {
+        Self(NEXT_ITEM.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Makes sure generate never returns the given id or any id below it. Call this
+    /// with the highest id in a save file once it is loaded.
+    pub fn reserve_through(last: Self) {
+        NEXT_ITEM.fetch_max(last.0.saturating_add(1), Ordering::Relaxed);
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Identifies a kind of item, such as Potion or Iron Sword. Every instance of the
+/// same kind of item shares a template id, which is set by the game data.
+pub struct TemplateId(pub usize);
+
+impl fmt::Display for TemplateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_ids_are_unique() {
+        let ids = (0..100).map(|_| ItemId::generate()).collect::<Vec<_>>();
+        assert!((1..ids.len()).all(|index| !ids[..index].contains(&ids[index])));
+        assert_ne!(EntityId::generate(), EntityId::generate());
+    }
+
+    #[test]
+    fn reserved_ids_are_never_generated() {
+        ItemId::reserve_through(ItemId(10_000));
+        assert!(ItemId::generate() > ItemId(10_000));
+        EntityId::reserve_through(EntityId(10_000));
+        EntityId::reserve_through(EntityId(5));
+        assert!(EntityId::generate() > EntityId(10_000));
+    }
+}
@@ -3,6 +3,7 @@
 use crate::stats::{Stat, StatKind, Stats};
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
 /// Contains the data a single level stores. This includes the current level,
 /// the current experience an enitity has, and how much experience is required
 /// for the next level.
@@ -24,12 +25,14 @@ impl LevelData {
     /// The exponent determines the gap between the levels. The larger the exponent,
     /// the larger the gap.
     const EXPONENT: f64 = 1.5;
+    /// The highest level an entity can reach.
+    pub const MAX_LEVEL: u8 = 99;
 
     /// Create an instance of LevelData given a level and current_experience.
     /// experience_for_next_level is automatically calculated.
     pub fn new(level: u8, current_experience: usize) -> Self {
         let experience_for_next_level = Self::experience_for_level(level);
-        match ((1..=99).contains(&level), current_experience < experience_for_next_level) {
+        match ((1..=Self::MAX_LEVEL).contains(&level), current_experience < experience_for_next_level) {
             (true, true) => Self { level, current_experience, experience_for_next_level },
             (false, true) => panic!("Level must be between 1 and 99"),
             (true, false) => panic!("Current experience must be less than experience required for the next level"),
@@ -37,34 +40,58 @@ impl LevelData {
         }
     }
 
+    /// Get the current level.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Get the current experience.
+    pub fn current_experience(&self) -> usize {
+        self.current_experience
+    }
+
+    /// Get the experience required for the next level.
+    pub fn experience_for_next_level(&self) -> usize {
+        self.experience_for_next_level
+    }
+
     /// Sets the current experience. If the experience is greater than or equal to that required
     /// for the next level, increment the level, store the excess and recalculate the experience
-    /// required for the next level.
-    pub fn set_current_experience(&mut self, experience: usize) {
-        if experience < self.experience_for_next_level {
-            self.current_experience = experience;
+    /// required for the next level. Returns the number of levels gained. Experience
+    /// stops just short of the next level once MAX_LEVEL is reached.
+    pub fn set_current_experience(&mut self, experience: usize) -> u8 {
+        if experience < self.experience_for_next_level || self.level >= Self::MAX_LEVEL {
+            self.current_experience = experience.min(self.experience_for_next_level - 1);
+            0
         } else {
-            // Calculate excess experience and store it, increment the level,
-            // and recalculate the experience required for the next level.
-            self.set_current_experience(self.current_experience - self.experience_for_next_level);
+            // Calculate excess experience, increment the level, recalculate the
+            // experience required for the next level and store the excess.
+            let excess = experience - self.experience_for_next_level;
             self.level += 1;
             self.experience_for_next_level = Self::experience_for_level(self.level);
+            1 + self.set_current_experience(excess)
         }
     }
 
+    /// Adds to the current experience, levelling up as many times as needed. Returns
+    /// the number of levels gained.
+    pub fn add_experience(&mut self, experience: usize) -> u8 {
+        self.set_current_experience(self.current_experience.saturating_add(experience))
+    }
+
     /// Calculates the amount of experience required to level up given the current level.
     /// The formula used is as follows:
     /// floor(baseXP * (currentLevel ^ exponent))
     /// This creates a simple curve that gets steeper as we increase the level.
     pub fn experience_for_level(level: u8) -> usize {
         let level = level as f64;
-        (Self::BASE_XP * ((level as f64).powf(Self::EXPONENT))).floor() as _
+        (Self::BASE_XP * (level.powf(Self::EXPONENT))).floor() as _
     }
 
     /// Creates a new stat table that represents the increase in stats after a level up.
     pub fn create_stat_table(&self) -> Stats {
         let mut stats = Stats::default();
-        stats.stats.iter_mut().for_each(|stat| {
+        stats.iter_mut().for_each(|stat| {
             let kind = {
                 let v = stat.base() + (stat.stat_growth.roll() as usize);
                 match stat.kind {
@@ -78,3 +105,32 @@ impl LevelData {
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn experience_carries_over_several_levels() {
+        let mut level_data = LevelData::new(1, 0);
+        let needed = LevelData::experience_for_level(1) + LevelData::experience_for_level(2);
+        assert_eq!(level_data.add_experience(needed + 5), 2);
+        assert_eq!(level_data.level(), 3);
+        assert_eq!(level_data.current_experience(), 5);
+        assert_eq!(
+            level_data.experience_for_next_level(),
+            LevelData::experience_for_level(3)
+        );
+    }
+
+    #[test]
+    fn experience_stops_at_the_max_level() {
+        let mut level_data = LevelData::new(LevelData::MAX_LEVEL, 0);
+        assert_eq!(level_data.add_experience(usize::MAX), 0);
+        assert_eq!(level_data.level(), LevelData::MAX_LEVEL);
+        assert_eq!(
+            level_data.current_experience(),
+            level_data.experience_for_next_level() - 1
+        );
+    }
+}
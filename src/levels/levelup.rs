@@ -0,0 +1,70 @@
+use super::LevelData;
+use crate::{
+    entities::Class,
+    ids::EntityId,
+    skills::{SkillEvent, SkillSet},
+    stats::{Stats, StatsDiff},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+/// Represents what happened to an entity when it gained experience. Shown to the
+/// player after battles and when experience is granted by items.
+pub struct LevelUpReport {
+    /// The id of the entity that gained the experience.
+    pub entity_id: EntityId,
+    /// The experience gained.
+    pub experience: usize,
+    /// The level before gaining the experience.
+    pub old_level: u8,
+    /// The level after gaining the experience.
+    pub new_level: u8,
+    /// The change in the stats from every level gained.
+    pub stats: StatsDiff,
+    /// The skills learned, or that need room to be learned, from every level gained.
+    pub skills: Vec<SkillEvent>,
+}
+
+impl LevelUpReport {
+    /// The number of levels gained.
+    pub fn levels_gained(&self) -> u8 {
+        self.new_level - self.old_level
+    }
+}
+
+/// Adds experience to the level data of an entity. For every level gained, the stats
+/// grow and the skills the class teaches at that level are learned, if the entity has
+/// a class and skills. Used by Entity::gain_experience and the ecs systems alike.
+pub fn level_up(
+    entity_id: EntityId,
+    experience: usize,
+    level_data: &mut LevelData,
+    stats: &mut Stats,
+    class: Option<&Class>,
+    mut skills: Option<&mut SkillSet>,
+) -> LevelUpReport {
+    let before = stats.clone();
+    let old_level = level_data.level();
+    level_data.add_experience(experience);
+    let new_level = level_data.level();
+
+    let mut events = Vec::new();
+    for level in old_level + 1..=new_level {
+        stats.grow();
+        if let (Some(class), Some(skills)) = (class, skills.as_deref_mut()) {
+            events.extend(
+                class
+                    .skills_at(level)
+                    .map(|skill| skills.learn(skill.clone())),
+            );
+        }
+    }
+
+    LevelUpReport {
+        entity_id,
+        experience,
+        old_level,
+        new_level,
+        stats: before.diff(stats),
+        skills: events,
+    }
+}
@@ -1,3 +1,5 @@
 mod level;
+mod levelup;
 
 pub use level::LevelData;
+pub use levelup::{level_up, LevelUpReport};
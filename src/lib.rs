@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate derive_builder;
+
+pub mod combat;
+pub mod crafting;
+#[cfg(feature = "ecs")]
+pub mod ecs;
+pub mod effects;
+pub mod entities;
+pub mod ids;
+pub mod levels;
+pub mod locale;
+pub mod loot;
+pub mod misc;
+pub mod random;
+pub mod shop;
+pub mod skills;
+pub mod stats;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,35 @@
+use std::{error::Error, fmt, io};
+
+#[derive(Debug)]
+/// Represents the ways loading a locale table can fail.
+pub enum LocaleError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The line with the given number (starting at 1) is not a comment, blank, or of
+    /// the form key = text.
+    Parse(usize),
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read the locale file: {}", error),
+            Self::Parse(line) => write!(f, "Line {} of the locale file is not key = text", line),
+        }
+    }
+}
+
+impl Error for LocaleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for LocaleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
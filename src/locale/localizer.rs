@@ -0,0 +1,151 @@
+use super::Message;
+use crate::{entities::Entity, misc::Item, stats::StatName};
+use std::borrow::Cow;
+
+/// Resolves localization keys into display text for a single language. Only get has to
+/// be implemented; the other methods build on it and fall back to the text stored on
+/// the value, or to English, when a key is missing.
+pub trait Localizer {
+    /// Get the text of the given key, if there is any.
+    fn get(&self, key: &str) -> Option<Cow<'_, str>>;
+
+    /// Get the text of the given key with every {name} replaced by the value of the
+    /// argument with that name. The fallback is used if the key is missing. The values
+    /// are inserted as they are, so a value containing {name} is not replaced again.
+    /// Placeholders without an argument are left in the text.
+    fn format(&self, key: &str, fallback: &str, args: &[(&str, String)]) -> String {
+        match self.get(key) {
+            Some(template) => fill(&template, args),
+            None => fill(fallback, args),
+        }
+    }
+
+    /// Get the display name of a stat, such as HP for HealthPoints.
+    fn stat_name(&self, name: StatName) -> String {
+        self.get(&name.key())
+            .map(Cow::into_owned)
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Get the display name of an item.
+    fn item_name(&self, item: &Item) -> String {
+        item.name_key()
+            .and_then(|key| self.get(&key))
+            .map(Cow::into_owned)
+            .unwrap_or_else(|| item.name.to_string())
+    }
+
+    /// Get the description of an item.
+    fn item_desc(&self, item: &Item) -> String {
+        item.desc_key()
+            .and_then(|key| self.get(&key))
+            .map(Cow::into_owned)
+            .unwrap_or_else(|| item.desc.to_string())
+    }
+
+    /// Get the display name of an entity.
+    fn entity_name(&self, entity: &Entity) -> String {
+        entity
+            .name_key()
+            .and_then(|key| self.get(&key))
+            .map(Cow::into_owned)
+            .unwrap_or_else(|| entity.name.to_string())
+    }
+
+    /// Get the text of a battle message, with the names in it localized.
+    fn message(&self, message: &Message) -> String {
+        let args = message
+            .args()
+            .into_iter()
+            .map(|(name, value)| (name, value.localize(self)))
+            .collect::<Vec<_>>();
+        self.format(message.key(), message.fallback(), &args)
+    }
+}
+
+/// Replaces every {name} in the template by the value of the argument with that name,
+/// in a single pass over the template.
+fn fill(template: &str, args: &[(&str, String)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                text.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{locale::LocaleTable, misc::ItemKind, testing};
+
+    fn table() -> LocaleTable {
+        LocaleTable::parse(
+            "stat.HealthPoints = PV\n\
+             entity.hero.name = Héroïne\n\
+             entity..name = Personne\n\
+             item.potion.desc = Rend 20 PV\n\
+             item..name = Objet\n\
+             battle.level_up = {entity} atteint le niveau {level} !",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn arguments_are_filled_in_once() {
+        let args = [("name", "{other}".to_string()), ("other", "x".to_string())];
+        assert_eq!(
+            table().format("missing", "{name} and {other} {unknown}", &args),
+            "{other} and x {unknown}"
+        );
+        assert_eq!(table().format("missing", "{name", &args), "{name");
+    }
+
+    #[test]
+    fn names_fall_back_to_the_text_on_the_value() {
+        let table = table();
+        assert_eq!(table.stat_name(StatName::HealthPoints), "PV");
+        assert_eq!(table.stat_name(StatName::Attack), "Attack");
+
+        let named = testing::entity("Hero").key("hero").build().unwrap();
+        let unnamed = testing::entity("Slime").build().unwrap();
+        assert_eq!(table.entity_name(&named), "Héroïne");
+        assert_eq!(table.entity_name(&unnamed), "Slime");
+
+        let potion = testing::item(ItemKind::UsableEverywhere, 1)
+            .name("Potion")
+            .desc("Heals 20 HP")
+            .key("potion")
+            .build()
+            .unwrap();
+        let unkeyed = testing::item(ItemKind::UsableEverywhere, 2)
+            .build()
+            .unwrap();
+        assert_eq!(table.item_name(&potion), "Potion");
+        assert_eq!(table.item_desc(&potion), "Rend 20 PV");
+        assert_eq!(table.item_name(&unkeyed), "Item");
+
+        let message = Message::LevelUp {
+            entity: &named,
+            level: 3,
+        };
+        assert_eq!(table.message(&message), "Héroïne atteint le niveau 3 !");
+    }
+}
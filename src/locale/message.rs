@@ -0,0 +1,161 @@
+use super::Localizer;
+use crate::{
+    combat::{AttackResult, HitOutcome},
+    entities::Entity,
+    levels::LevelUpReport,
+    stats::{StatEvent, StatName},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+/// Represents something that happened in battle which should be shown to the player.
+/// The text comes from the localizer, with the key of the message and the English
+/// fallback listed on every variant.
+pub enum Message<'a> {
+    /// battle.attack: {attacker} hits {defender} for {damage} damage!
+    Hit {
+        attacker: &'a Entity,
+        defender: &'a Entity,
+        damage: usize,
+    },
+    /// battle.critical: A critical hit! {attacker} hits {defender} for {damage} damage!
+    CriticalHit {
+        attacker: &'a Entity,
+        defender: &'a Entity,
+        damage: usize,
+    },
+    /// battle.miss: {attacker} misses {defender}.
+    Miss {
+        attacker: &'a Entity,
+        defender: &'a Entity,
+    },
+    /// battle.depleted: {entity} has run out of {stat}!
+    Depleted { entity: &'a Entity, stat: StatName },
+    /// battle.restored: {entity} has recovered {stat}.
+    Restored { entity: &'a Entity, stat: StatName },
+    /// battle.level_up: {entity} has reached level {level}!
+    LevelUp { entity: &'a Entity, level: u8 },
+}
+
+/// A value filled into the text of a message, localized when the message is.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Arg<'a> {
+    Entity(&'a Entity),
+    Stat(StatName),
+    Number(usize),
+}
+
+impl Arg<'_> {
+    /// Get the text of the argument in the language of the localizer.
+    pub(crate) fn localize<L: Localizer + ?Sized>(&self, localizer: &L) -> String {
+        match self {
+            Self::Entity(entity) => localizer.entity_name(entity),
+            Self::Stat(name) => localizer.stat_name(*name),
+            Self::Number(number) => number.to_string(),
+        }
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Get the messages describing an attack that has been applied to the defender:
+    /// one for every hit, and one if the HealthPoints of the defender were depleted.
+    pub fn attack(attacker: &'a Entity, defender: &'a Entity, result: &AttackResult) -> Vec<Self> {
+        let mut messages = result
+            .hits
+            .iter()
+            .map(|hit| match *hit {
+                HitOutcome::Miss => Self::Miss { attacker, defender },
+                HitOutcome::Hit {
+                    damage,
+                    critical: false,
+                } => Self::Hit {
+                    attacker,
+                    defender,
+                    damage,
+                },
+                HitOutcome::Hit {
+                    damage,
+                    critical: true,
+                } => Self::CriticalHit {
+                    attacker,
+                    defender,
+                    damage,
+                },
+            })
+            .collect::<Vec<_>>();
+        messages.extend(result.event.map(|event| Self::event(defender, event)));
+        messages
+    }
+
+    /// Get the message for a stat event of the given entity.
+    pub fn event(entity: &'a Entity, event: StatEvent) -> Self {
+        match event {
+            StatEvent::Depleted(stat) => Self::Depleted { entity, stat },
+            StatEvent::Restored(stat) => Self::Restored { entity, stat },
+        }
+    }
+
+    /// Get the message for the entity reaching a new level, if it gained any.
+    pub fn level_up(entity: &'a Entity, report: &LevelUpReport) -> Option<Self> {
+        (report.levels_gained() > 0).then_some(Self::LevelUp {
+            entity,
+            level: report.new_level,
+        })
+    }
+
+    /// Get the localization key of the message.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Hit { .. } => "battle.attack",
+            Self::CriticalHit { .. } => "battle.critical",
+            Self::Miss { .. } => "battle.miss",
+            Self::Depleted { .. } => "battle.depleted",
+            Self::Restored { .. } => "battle.restored",
+            Self::LevelUp { .. } => "battle.level_up",
+        }
+    }
+
+    /// Get the English text of the message, used when the key is missing.
+    pub fn fallback(&self) -> &'static str {
+        match self {
+            Self::Hit { .. } => "{attacker} hits {defender} for {damage} damage!",
+            Self::CriticalHit { .. } => {
+                "A critical hit! {attacker} hits {defender} for {damage} damage!"
+            }
+            Self::Miss { .. } => "{attacker} misses {defender}.",
+            Self::Depleted { .. } => "{entity} has run out of {stat}!",
+            Self::Restored { .. } => "{entity} has recovered {stat}.",
+            Self::LevelUp { .. } => "{entity} has reached level {level}!",
+        }
+    }
+
+    /// Get the values filled into the text of the message.
+    pub(crate) fn args(&self) -> Vec<(&'static str, Arg<'a>)> {
+        match *self {
+            Self::Hit {
+                attacker,
+                defender,
+                damage,
+            }
+            | Self::CriticalHit {
+                attacker,
+                defender,
+                damage,
+            } => vec![
+                ("attacker", Arg::Entity(attacker)),
+                ("defender", Arg::Entity(defender)),
+                ("damage", Arg::Number(damage)),
+            ],
+            Self::Miss { attacker, defender } => vec![
+                ("attacker", Arg::Entity(attacker)),
+                ("defender", Arg::Entity(defender)),
+            ],
+            Self::Depleted { entity, stat } | Self::Restored { entity, stat } => {
+                vec![("entity", Arg::Entity(entity)), ("stat", Arg::Stat(stat))]
+            }
+            Self::LevelUp { entity, level } => vec![
+                ("entity", Arg::Entity(entity)),
+                ("level", Arg::Number(level as usize)),
+            ],
+        }
+    }
+}
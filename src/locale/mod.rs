@@ -0,0 +1,9 @@
+mod localeerror;
+mod localizer;
+mod message;
+mod table;
+
+pub use localeerror::LocaleError;
+pub use localizer::Localizer;
+pub use message::Message;
+pub use table::LocaleTable;
@@ -0,0 +1,89 @@
+use super::{LocaleError, Localizer};
+use std::{borrow::Cow, collections::HashMap, fs, path::Path};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+/// A localizer backed by a table of keys and text, usually loaded from one file per
+/// language. The file has one entry per line in the form key = text. Blank lines and
+/// lines starting with # are ignored. For example:
+/// # English
+/// stat.HealthPoints = HP
+/// item.potion.name = Potion
+/// battle.attack = {attacker} hits {defender} for {damage} damage!
+pub struct LocaleTable(HashMap<String, String>);
+
+impl LocaleTable {
+    /// Create a new, empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a table from the file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LocaleError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Reads a table from the contents of a locale file.
+    pub fn parse(source: &str) -> Result<Self, LocaleError> {
+        let mut table = Self::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once('=') {
+                Some((key, text)) if !key.trim().is_empty() => {
+                    table.insert(key.trim(), text.trim());
+                }
+                _ => return Err(LocaleError::Parse(index + 1)),
+            }
+        }
+        Ok(table)
+    }
+
+    /// Sets the text of a key, replacing the text it had before.
+    pub fn insert(&mut self, key: impl Into<String>, text: impl Into<String>) {
+        self.0.insert(key.into(), text.into());
+    }
+
+    /// Get the number of keys in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determines whether the table has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Localizer for LocaleTable {
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.0.get(key).map(|text| Cow::Borrowed(text.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_parsed_line_by_line() {
+        let table = LocaleTable::parse("# English\n\nstat.HealthPoints = HP\n a = b = c ").unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get("stat.HealthPoints").as_deref(), Some("HP"));
+        assert_eq!(table.get("a").as_deref(), Some("b = c"));
+        assert_eq!(table.get("missing"), None);
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        assert!(matches!(
+            LocaleTable::parse("a = b\nno separator"),
+            Err(LocaleError::Parse(2))
+        ));
+        assert!(matches!(
+            LocaleTable::parse(" = text"),
+            Err(LocaleError::Parse(1))
+        ));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::misc::Item;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Represents the rewards rolled from a loot table.
+pub struct Drops {
+    /// The items dropped. Items dropped several times appear once per copy.
+    pub items: Vec<Item>,
+    /// The gold dropped.
+    pub gold: usize,
+    /// The experience given.
+    pub experience: usize,
+}
+
+impl Drops {
+    /// Adds the rewards of other to these rewards. Gold and experience saturate
+    /// instead of overflowing.
+    pub fn merge(&mut self, other: Drops) {
+        self.items.extend(other.items);
+        self.gold = self.gold.saturating_add(other.gold);
+        self.experience = self.experience.saturating_add(other.experience);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merging_saturates() {
+        let mut drops = Drops {
+            gold: usize::MAX - 1,
+            experience: 5,
+            ..Drops::default()
+        };
+        drops.merge(Drops {
+            gold: 10,
+            experience: usize::MAX,
+            ..Drops::default()
+        });
+        assert_eq!((drops.gold, drops.experience), (usize::MAX, usize::MAX));
+    }
+}
@@ -0,0 +1,19 @@
+use std::{borrow::Cow, error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Represents the ways a loot table can be invalid. Tables that pass validation
+/// can be rolled without panicking.
+pub enum LootError {
+    /// An amount of the table is not in dice notation.
+    InvalidDice(Cow<'static, str>),
+}
+
+impl fmt::Display for LootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDice(dice) => write!(f, "{:?} is not in dice notation", dice),
+        }
+    }
+}
+
+impl Error for LootError {}
@@ -0,0 +1,9 @@
+mod drops;
+mod looterror;
+mod rewards;
+mod table;
+
+pub use drops::Drops;
+pub use looterror::LootError;
+pub use rewards::{RewardConfig, RewardReport};
+pub use table::{LootDrop, LootEntry, LootTable};
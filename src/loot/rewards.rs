@@ -0,0 +1,36 @@
+use crate::{levels::LevelUpReport, misc::Item};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Determines how the rewards of a battle are shared among the party. Alive active
+/// members receive the full experience, knocked out and reserved members receive a
+/// fraction of it, and members that are dead or petrified receive nothing.
+pub struct RewardConfig {
+    /// The fraction of the experience given to members of the reserved party.
+    pub reserved_share: f64,
+    /// The fraction of the experience given to active members that are knocked out.
+    /// None by default, as they did not survive the battle.
+    pub knocked_out_share: f64,
+    /// The fraction of the experience given on top to members that landed a final blow.
+    pub final_blow_bonus: f64,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        Self {
+            reserved_share: 0.5,
+            knocked_out_share: 0.0,
+            final_blow_bonus: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Represents the rewards handed out to the party after a battle.
+pub struct RewardReport {
+    /// The gold added to the party.
+    pub gold: usize,
+    /// The items dropped, which are left to the caller to put away.
+    pub items: Vec<Item>,
+    /// What happened to every member that gained experience.
+    pub level_ups: Vec<LevelUpReport>,
+}
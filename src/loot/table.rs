@@ -0,0 +1,261 @@
+use crate::{
+    loot::{Drops, LootError},
+    misc::Item,
+    random,
+};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents what a single entry of a loot table drops.
+pub enum LootDrop {
+    /// Drops copies of an item.
+    Item(Box<Item>),
+    /// Rolls a nested loot table, once per copy.
+    Table(LootTable),
+    /// Drops nothing. Used to give a table a chance of dropping nothing.
+    Nothing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a single entry of a loot table.
+pub struct LootEntry {
+    /// What the entry drops.
+    pub drop: LootDrop,
+    /// The chance of this entry being picked, relative to the other entries.
+    pub weight: u32,
+    /// How many copies are dropped, in dice notation such as 1d3.
+    pub quantity: Cow<'static, str>,
+}
+
+impl LootEntry {
+    /// Create an entry that drops an item, weighted by the rarity of the item.
+    /// Fails if the quantity is not in dice notation.
+    pub fn item(item: Item, quantity: impl Into<Cow<'static, str>>) -> Result<Self, LootError> {
+        let entry = Self {
+            weight: item.rarity.drop_weight(),
+            drop: LootDrop::Item(Box::new(item)),
+            quantity: quantity.into(),
+        };
+        entry.validate()?;
+        Ok(entry)
+    }
+
+    /// Create an entry that rolls a nested table once.
+    pub fn table(table: LootTable, weight: u32) -> Self {
+        Self {
+            drop: LootDrop::Table(table),
+            weight,
+            quantity: "1".into(),
+        }
+    }
+
+    /// Create an entry that drops nothing.
+    pub fn nothing(weight: u32) -> Self {
+        Self {
+            drop: LootDrop::Nothing,
+            weight,
+            quantity: "1".into(),
+        }
+    }
+
+    /// Checks that the quantity of this entry, and every amount of its nested table,
+    /// is in dice notation.
+    pub fn validate(&self) -> Result<(), LootError> {
+        check(&self.quantity)?;
+        match &self.drop {
+            LootDrop::Table(table) => table.validate(),
+            LootDrop::Item(_) | LootDrop::Nothing => Ok(()),
+        }
+    }
+
+    /// Rolls the quantity of this entry and drops that many copies.
+    fn roll(&self) -> Drops {
+        let quantity = random::roll(&self.quantity).max(0) as usize;
+        let mut drops = Drops::default();
+        match &self.drop {
+            LootDrop::Item(item) => {
+                drops.items = (0..quantity).map(|_| item.instantiate()).collect()
+            }
+            LootDrop::Table(table) => (0..quantity).for_each(|_| drops.merge(table.roll())),
+            LootDrop::Nothing => {}
+        }
+        drops
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents the rewards an enemy can drop when defeated. Every roll of the table
+/// drops all of the guaranteed entries and picks from the weighted entries. Every
+/// amount is written in dice notation and rolled through the global random number
+/// generator, so drops are reproducible after random::seed. Rolling panics on amounts
+/// that are not in dice notation, so tables built by hand should be validated first.
+pub struct LootTable {
+    /// Entries that are always dropped.
+    pub guaranteed: Vec<LootEntry>,
+    /// Entries that are picked by weight.
+    pub entries: Vec<LootEntry>,
+    /// How many times to pick from the weighted entries.
+    pub picks: Cow<'static, str>,
+    /// The gold dropped.
+    pub gold: Cow<'static, str>,
+    /// The experience given.
+    pub experience: Cow<'static, str>,
+}
+
+impl Default for LootTable {
+    fn default() -> Self {
+        Self {
+            guaranteed: Vec::new(),
+            entries: Vec::new(),
+            picks: "1".into(),
+            gold: "0".into(),
+            experience: "0".into(),
+        }
+    }
+}
+
+impl LootTable {
+    /// Create a new loot table that picks once from the entries, with no guaranteed
+    /// drops, gold or experience. Fails if an amount of an entry is not in dice notation.
+    pub fn new(entries: Vec<LootEntry>) -> Result<Self, LootError> {
+        let table = Self {
+            entries,
+            ..Self::default()
+        };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Checks that every amount of the table and of its entries is in dice notation.
+    pub fn validate(&self) -> Result<(), LootError> {
+        check(&self.picks)?;
+        check(&self.gold)?;
+        check(&self.experience)?;
+        self.guaranteed
+            .iter()
+            .chain(&self.entries)
+            .try_for_each(LootEntry::validate)
+    }
+
+    /// Rolls the table, returning everything dropped.
+    pub fn roll(&self) -> Drops {
+        let mut drops = Drops {
+            items: Vec::new(),
+            gold: random::roll(&self.gold).max(0) as usize,
+            experience: random::roll(&self.experience).max(0) as usize,
+        };
+        self.guaranteed
+            .iter()
+            .for_each(|entry| drops.merge(entry.roll()));
+
+        let weights = self
+            .entries
+            .iter()
+            .map(|entry| entry.weight)
+            .collect::<Vec<_>>();
+        for _ in 0..random::roll(&self.picks).max(0) {
+            if let Some(index) = random::weighted(&weights) {
+                drops.merge(self.entries[index].roll());
+            }
+        }
+        drops
+    }
+}
+
+/// Fails with the dice if they are not in dice notation.
+fn check(dice: &str) -> Result<(), LootError> {
+    if random::is_dice(dice) {
+        Ok(())
+    } else {
+        Err(LootError::InvalidDice(dice.to_owned().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::ItemKind, testing};
+
+    fn potion() -> Item {
+        testing::item(ItemKind::UsableEverywhere, 7)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn guaranteed_entries_always_drop() {
+        let table = LootTable {
+            guaranteed: vec![LootEntry::item(potion(), "2").unwrap()],
+            gold: "10".into(),
+            experience: "3d1".into(),
+            ..LootTable::default()
+        };
+        let drops = table.roll();
+        assert_eq!(drops.items.len(), 2);
+        assert_ne!(drops.items[0].id, drops.items[1].id);
+        assert_eq!(drops.gold, 10);
+        assert_eq!(drops.experience, 3);
+    }
+
+    #[test]
+    fn entries_without_weight_are_never_picked() {
+        let table = LootTable {
+            entries: vec![
+                LootEntry {
+                    weight: 0,
+                    ..LootEntry::item(potion(), "1").unwrap()
+                },
+                LootEntry::nothing(1),
+            ],
+            picks: "20".into(),
+            ..LootTable::default()
+        };
+        assert!(table.roll().items.is_empty());
+    }
+
+    #[test]
+    fn nested_tables_are_rolled_once_per_copy() {
+        let inner = LootTable {
+            guaranteed: vec![LootEntry::item(potion(), "1").unwrap()],
+            gold: "5".into(),
+            ..LootTable::default()
+        };
+        let table = LootTable::new(vec![LootEntry {
+            quantity: "3".into(),
+            ..LootEntry::table(inner, 1)
+        }])
+        .unwrap();
+        let drops = table.roll();
+        assert_eq!(drops.items.len(), 3);
+        assert_eq!(drops.gold, 15);
+    }
+
+    #[test]
+    fn empty_tables_drop_nothing() {
+        let drops = LootTable::default().roll();
+        assert!(drops.items.is_empty());
+        assert_eq!((drops.gold, drops.experience), (0, 0));
+    }
+
+    #[test]
+    fn invalid_dice_are_rejected() {
+        assert_eq!(
+            LootEntry::item(potion(), "a few"),
+            Err(LootError::InvalidDice("a few".into()))
+        );
+        let inner = LootTable {
+            gold: "lots".into(),
+            ..LootTable::default()
+        };
+        assert_eq!(
+            LootTable::new(vec![LootEntry::table(inner, 1)]),
+            Err(LootError::InvalidDice("lots".into()))
+        );
+        let table = LootTable {
+            guaranteed: vec![LootEntry::item(potion(), "1d3+1").unwrap()],
+            picks: "2".into(),
+            ..LootTable::default()
+        };
+        assert_eq!(table.validate(), Ok(()));
+    }
+}
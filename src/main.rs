@@ -1,11 +1,4 @@
-#[macro_use]
-extern crate derive_builder;
-mod entities;
-mod levels;
-mod misc;
-mod stats;
-
-use crate::{entities::EntityBuilder, levels::LevelData, stats::Stats};
+use rpg_engine::{entities::EntityBuilder, levels::LevelData, stats::Stats};
 
 // TODO:
 // UnitTests, Integration Tests
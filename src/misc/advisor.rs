@@ -0,0 +1,190 @@
+use super::{EquipmentType, Inventory, Item};
+use crate::{
+    entities::Entity,
+    ids::ItemId,
+    stats::{StatName, Stats, StatsDiff},
+};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Represents how much each stat is worth when judging equipment. The score of some
+/// stats is the sum of the calculated value of every weighted stat times its weight.
+pub struct StatWeights(Vec<(StatName, f64)>);
+
+impl StatWeights {
+    /// Weights that only care about a single stat, such as maximizing Attack.
+    pub fn maximize(name: StatName) -> Self {
+        Self(vec![(name, 1.0)])
+    }
+
+    /// Weights that value every built-in battle stat equally.
+    pub fn balanced() -> Self {
+        Self(
+            StatName::BUILTIN
+                .iter()
+                .filter(|&&name| name != StatName::Friendship)
+                .map(|&name| (name, 1.0))
+                .collect(),
+        )
+    }
+
+    /// Sets the weight of a stat, replacing any weight it already had.
+    pub fn with(mut self, name: StatName, weight: f64) -> Self {
+        self.0.retain(|(weighted, _)| *weighted != name);
+        self.0.push((name, weight));
+        self
+    }
+
+    /// Get the score of the given stats.
+    pub fn score(&self, stats: &Stats) -> f64 {
+        self.0
+            .iter()
+            .map(|&(name, weight)| stats.value(name) * weight)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents what would happen if an entity swapped the piece in one of its slots
+/// for another item.
+pub struct Suggestion {
+    /// The id of the item that would be equipped.
+    pub item_id: ItemId,
+    /// The slot the item goes into.
+    pub slot: EquipmentType,
+    /// The change in the effective stats of the entity.
+    pub diff: StatsDiff,
+    /// The change in the score of the effective stats of the entity.
+    pub score: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Compares equipment for an entity and picks the best loadout from an inventory,
+/// judging the effective stats of the entity by a set of weights.
+pub struct EquipmentAdvisor {
+    /// How much each stat is worth.
+    pub weights: StatWeights,
+}
+
+impl EquipmentAdvisor {
+    /// Create a new advisor given the weights it judges stats by.
+    pub fn new(weights: StatWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Get the change in the effective stats of an entity if it equipped the item,
+    /// replacing whatever is in that slot.
+    pub fn compare(&self, entity: &Entity, item: &Item) -> StatsDiff {
+        entity.effective_stats().diff(&Self::swapped(entity, item))
+    }
+
+    /// Get a suggestion for every piece of equipment in the inventory the entity can
+    /// use, best first.
+    pub fn suggestions(&self, entity: &Entity, inventory: &Inventory) -> Vec<Suggestion> {
+        let before = entity.effective_stats();
+        let mut suggestions = inventory
+            .iter()
+            .filter(|item| item.slot().is_some() && entity.can_equip(item).is_ok())
+            .map(|item| {
+                let after = Self::swapped(entity, item);
+                Suggestion {
+                    item_id: item.id,
+                    slot: item.slot().unwrap(),
+                    diff: before.diff(&after),
+                    score: self.weights.score(&after) - self.weights.score(&before),
+                }
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        suggestions
+    }
+
+    /// Equips the best piece from the inventory into every slot of the entity, moving
+    /// replaced pieces back into the inventory. A slot is only changed if it raises the
+    /// score. Returns the change in the effective stats of the entity.
+    pub fn optimize(&self, entity: &mut Entity, inventory: &mut Inventory) -> StatsDiff {
+        let before = entity.effective_stats();
+        for slot in EquipmentType::ALL.iter().copied() {
+            let current = self.weights.score(&entity.effective_stats());
+            let best = inventory
+                .equipment(slot)
+                .filter(|item| entity.can_equip(item).is_ok())
+                .map(|item| (item.id, self.weights.score(&Self::swapped(entity, item))))
+                .filter(|&(_, score)| score > current)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((id, _)) = best {
+                let item = inventory.get(id).cloned().unwrap();
+                if let Ok(previous) = entity.equip(item) {
+                    inventory.remove_by_id(id);
+                    if let Some(previous) = previous {
+                        inventory.add(previous).ok();
+                    }
+                }
+            }
+        }
+        before.diff(&entity.effective_stats())
+    }
+
+    /// Get the effective stats of an entity as if it had equipped the item.
+    fn swapped(entity: &Entity, item: &Item) -> Stats {
+        let mut equipment = entity.equipment().clone();
+        equipment.equip(item.clone()).ok();
+        &entity.stats + &equipment.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stats::StatDelta, testing};
+
+    #[test]
+    fn compare_reports_the_change_of_swapping_a_piece() {
+        let mut entity = testing::entity("Hero").build().unwrap();
+        let old = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 2)]);
+        entity.equip(old).unwrap();
+        let new = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 5)]);
+        let advisor = EquipmentAdvisor::new(StatWeights::maximize(StatName::Attack));
+        let diff = advisor.compare(&entity, &new);
+        assert_eq!(diff.delta(StatName::Attack), StatDelta::Static(3));
+    }
+
+    #[test]
+    fn suggestions_are_sorted_best_first() {
+        let entity = testing::entity("Hero").build().unwrap();
+        let weak = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 1)]);
+        let strong = testing::equipment(EquipmentType::Feet, &[(StatName::Attack, 4)]);
+        let strong_id = strong.id;
+        let inventory = Inventory::new(vec![weak, strong]).unwrap();
+        let advisor = EquipmentAdvisor::new(StatWeights::maximize(StatName::Attack));
+        let suggestions = advisor.suggestions(&entity, &inventory);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].item_id, strong_id);
+        assert_eq!(suggestions[0].score, 4.0);
+    }
+
+    #[test]
+    fn optimize_equips_the_best_pieces_and_returns_the_rest() {
+        let mut entity = testing::entity("Hero").build().unwrap();
+        let worn = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 1)]);
+        let worn_id = worn.id;
+        entity.equip(worn).unwrap();
+        let better = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 3)]);
+        let best = testing::equipment(EquipmentType::Head, &[(StatName::Attack, 6)]);
+        let best_id = best.id;
+        let mut inventory = Inventory::new(vec![better, best]).unwrap();
+
+        let advisor = EquipmentAdvisor::new(StatWeights::maximize(StatName::Attack));
+        let diff = advisor.optimize(&mut entity, &mut inventory);
+        assert_eq!(diff.delta(StatName::Attack), StatDelta::Static(5));
+        assert_eq!(
+            entity
+                .equipment()
+                .get(EquipmentType::Head)
+                .map(|item| item.id),
+            Some(best_id)
+        );
+        assert!(inventory.get(worn_id).is_some());
+        assert_eq!(inventory.len(), 2);
+    }
+}
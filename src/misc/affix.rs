@@ -0,0 +1,188 @@
+use super::{EquipmentType, Item, Rarity};
+use std::borrow::Cow;
+
+use crate::{
+    random,
+    stats::{Modifier, StatName},
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents where the name of an affix goes in the name of the item.
+pub enum AffixKind {
+    /// Goes before the name, such as Sturdy Iron Helm.
+    Prefix,
+    /// Goes after the name, such as Iron Helm of the Bear.
+    Suffix,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a random bonus that can be rolled onto generated equipment.
+pub struct Affix {
+    /// The name added to the name of the item.
+    pub name: Cow<'static, str>,
+    /// Whether the name goes before or after the name of the item.
+    pub kind: AffixKind,
+    /// The modifiers added to the stats of the item.
+    pub modifiers: Vec<(StatName, Modifier)>,
+    /// The slots of the equipment this affix can be rolled on. Every slot is allowed
+    /// if this is None.
+    pub slots: Option<Vec<EquipmentType>>,
+    /// The lowest item level this affix can be rolled at.
+    pub min_level: u8,
+    /// How likely this affix is to be picked compared to the others.
+    pub weight: u32,
+}
+
+impl Affix {
+    /// Whether this affix can be rolled on equipment of the given slot and level.
+    pub fn allowed(&self, slot: EquipmentType, level: u8) -> bool {
+        level >= self.min_level
+            && self
+                .slots
+                .as_ref()
+                .is_none_or(|slots| slots.contains(&slot))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+/// Represents every affix that can be rolled onto generated equipment. Rolls go
+/// through the global random number generator, so they can be reproduced by seeding it.
+pub struct AffixPool(pub Vec<Affix>);
+
+impl AffixPool {
+    /// Create a new pool from a list of affixes.
+    pub fn new(affixes: Vec<Affix>) -> Self {
+        Self(affixes)
+    }
+
+    /// Generates a new instance of the item with a random rarity. See generate_with.
+    pub fn generate(&self, template: &Item, level: u8) -> Item {
+        self.generate_with(template, level, Rarity::roll())
+    }
+
+    /// Generates a new instance of the item with the given rarity. Equipment gets as
+    /// many affixes as the rarity allows, alternating between prefixes and suffixes,
+    /// from the affixes allowed for its slot and the item level. No affix is rolled
+    /// twice, so there may be fewer if the pool runs out. Items that are not equipment
+    /// only get the rarity.
+    pub fn generate_with(&self, template: &Item, level: u8, rarity: Rarity) -> Item {
+        let mut item = template.instantiate();
+        item.rarity = rarity;
+        let slot = match item.slot() {
+            Some(slot) => slot,
+            None => return item,
+        };
+
+        let mut rolled: Vec<&Affix> = Vec::new();
+        for index in 0..rarity.affixes() {
+            let kind = if index % 2 == 0 {
+                AffixKind::Prefix
+            } else {
+                AffixKind::Suffix
+            };
+            let choices = self
+                .0
+                .iter()
+                .filter(|affix| affix.kind == kind && affix.allowed(slot, level))
+                .filter(|affix| !rolled.contains(affix))
+                .collect::<Vec<_>>();
+            let weights = choices.iter().map(|affix| affix.weight).collect::<Vec<_>>();
+            if let Some(pick) = random::weighted(&weights) {
+                rolled.push(choices[pick]);
+            }
+        }
+
+        for affix in &rolled {
+            for &(name, modifier) in &affix.modifiers {
+                item.stats.add_modifier(name, modifier);
+            }
+        }
+        let names = |kind| {
+            rolled
+                .iter()
+                .filter(move |affix| affix.kind == kind)
+                .map(|affix| affix.name.as_ref())
+        };
+        let name = names(AffixKind::Prefix)
+            .chain(Some(item.name.as_ref()))
+            .chain(names(AffixKind::Suffix))
+            .collect::<Vec<_>>()
+            .join(" ");
+        item.name = name.into();
+        item.affixes = rolled.into_iter().cloned().collect();
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::ItemKind, testing};
+
+    fn affix(name: &'static str, kind: AffixKind, min_level: u8) -> Affix {
+        Affix {
+            name: name.into(),
+            kind,
+            modifiers: vec![(StatName::Attack, Modifier::Plus(5.0))],
+            slots: Some(vec![EquipmentType::Weapon]),
+            min_level,
+            weight: 1,
+        }
+    }
+
+    fn pool() -> AffixPool {
+        AffixPool::new(vec![
+            affix("Sharp", AffixKind::Prefix, 1),
+            affix("Ancient", AffixKind::Prefix, 50),
+            affix("of Might", AffixKind::Suffix, 1),
+        ])
+    }
+
+    fn sword() -> Item {
+        testing::item(ItemKind::Equipment(EquipmentType::Weapon), 1)
+            .name("Sword")
+            .stats(testing::stats(&[(StatName::Attack, 10)]))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn affixes_need_the_slot_and_level() {
+        let sharp = affix("Sharp", AffixKind::Prefix, 10);
+        assert!(sharp.allowed(EquipmentType::Weapon, 10));
+        assert!(!sharp.allowed(EquipmentType::Weapon, 9));
+        assert!(!sharp.allowed(EquipmentType::Head, 10));
+        let anywhere = Affix {
+            slots: None,
+            ..sharp
+        };
+        assert!(anywhere.allowed(EquipmentType::Head, 10));
+    }
+
+    #[test]
+    fn rarity_decides_the_number_of_affixes() {
+        let template = sword();
+        let common = pool().generate_with(&template, 1, Rarity::Common);
+        assert!(common.affixes.is_empty());
+        assert_eq!(common.name, "Sword");
+        assert_ne!(common.id, template.id);
+
+        let rare = pool().generate_with(&template, 1, Rarity::Rare);
+        assert_eq!(rare.rarity, Rarity::Rare);
+        assert_eq!(rare.name, "Sharp Sword of Might");
+        assert_eq!(rare.stats.value(StatName::Attack), 20.0);
+
+        let legendary = pool().generate_with(&template, 1, Rarity::Legendary);
+        assert_eq!(legendary.affixes.len(), 2);
+    }
+
+    #[test]
+    fn only_equipment_gets_affixes() {
+        let potion = testing::item(ItemKind::UsableEverywhere, 2)
+            .build()
+            .unwrap();
+        let generated = pool().generate_with(&potion, 99, Rarity::Epic);
+        assert_eq!(generated.rarity, Rarity::Epic);
+        assert!(generated.affixes.is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+use crate::stats::StatName;
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents the elemental affinity of an attack.
+pub enum Element {
+    /// Attacks without any affinity. Nothing resists these.
+    #[default]
+    Neutral,
+    Fire,
+    Ice,
+    Lightning,
+    Earth,
+    Wind,
+    Light,
+    Dark,
+}
+
+impl Element {
+    /// Get the custom stat that resists this element, such as FireResistance, if any
+    /// entity has been given one. Its value is the percentage of damage resisted.
+    pub fn resistance(&self) -> Option<StatName> {
+        match self {
+            Self::Neutral => None,
+            element => StatName::find(&format!("{:?}Resistance", element)),
+        }
+    }
+}
@@ -1,57 +1,280 @@
 #![allow(dead_code)]
 
-use super::{Item, ItemKind};
+use super::{EquipmentType, Item, ItemError, SetBonus, SetProgress, Weapon};
+use crate::{ids::ItemId, stats::Stats};
+use std::borrow::Cow;
 
 #[derive(Debug, Clone, PartialEq)]
-/// Represents the five possible equipment that an entity can have. This includes:
-/// Accessory
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents the six possible equipment that an entity can have. This includes:
 /// Weapon
+/// Accessory
 /// Armour
 /// Legs
 /// Feet
+/// Head
 /// Note: Currently there is no check to make sure that each Equipment piece is a unique
 /// EquipmentType
-pub struct Equipment(Vec<Item>);
+/// The sets worn are detected whenever items are added or removed.
+pub struct Equipment {
+    /// The equipped items.
+    items: Vec<Item>,
+    /// The progress of every set with at least one piece equipped.
+    sets: Vec<SetProgress>,
+}
 
 impl Equipment {
+    /// The most pieces of equipment an entity can wear.
+    pub const MAX_ITEMS: usize = 6;
+
     /// Create a new instance of equipment given a list of equipment. This list must
-    /// be less than or equal to 5, and must only contain items where Item.kind == Equipment.
-    pub fn new(equipment: Vec<Item>) -> Self {
-        if equipment
-            .iter()
-            .filter(|item| !matches!(item.kind, ItemKind::Equipment(_)))
-            .count()
-            > 0
-        {
-            panic!("Equipment must be initialized with items that have the ItemKind of Equipment");
-        } else if equipment.len() > 5 {
-            panic!("There can be no more than five equipment on an entity.");
+    /// be less than or equal to 6, and must only contain items where Item.kind == Equipment.
+    /// Every item must have a different id.
+    pub fn new(equipment: Vec<Item>) -> Result<Self, ItemError> {
+        let mut new = Self {
+            items: Vec::with_capacity(equipment.len()),
+            sets: Vec::new(),
+        };
+        for item in equipment {
+            new.check(&item)?;
+            new.items.push(item);
         }
-        Self(equipment)
+        new.detect_sets();
+        Ok(new)
     }
 
-    /// Adds an item to the entity's equipment. Given that the item.kind == Equipment and
-    /// that the current equipment equipped is less than 5.
-    pub fn add(&mut self, item: Item) {
-        if !matches!(item.kind, ItemKind::Equipment(_)) {
-            panic!("Equipment must be initialized with items that have the ItemKind of Equipment");
-        } else if self.0.len() >= 5 {
-            panic!("There can be no more than five equipment on an entity.");
-        }
-        self.0.push(item);
+    /// Adds an item to the entity's equipment. Given that the item.kind == Equipment,
+    /// that the current equipment equipped is less than 6 and that no equipped item
+    /// has the same id.
+    pub fn add(&mut self, item: Item) -> Result<(), ItemError> {
+        self.check(&item)?;
+        self.items.push(item);
+        self.detect_sets();
+        Ok(())
     }
 
-    /// Given an id, returns a new vector with the elements with that id removed.
-    pub fn remove_by_id(&mut self, id: usize) -> Vec<Item> {
-        self.0
-            .clone()
-            .into_iter()
-            .filter(|item| item.id != id)
-            .collect::<Vec<Item>>()
+    /// Removes and returns the item with the given id.
+    pub fn remove_by_id(&mut self, id: ItemId) -> Option<Item> {
+        let index = self.items.iter().position(|item| item.id == id)?;
+        let item = self.items.remove(index);
+        self.detect_sets();
+        Some(item)
     }
 
     /// Removes the specified item from the equipment.
     pub fn remove(&mut self, item: Item) {
-        self.0 = self.remove_by_id(item.id);
+        self.remove_by_id(item.id);
+    }
+
+    /// Get the item equipped in the given slot.
+    pub fn get(&self, slot: EquipmentType) -> Option<&Item> {
+        self.items.iter().find(|item| item.slot() == Some(slot))
+    }
+
+    /// Equips an item into the slot of its EquipmentType, returning the item that was
+    /// previously equipped in that slot. Nothing changes if the item is not equipment
+    /// or an item with the same id is already equipped.
+    pub fn equip(&mut self, item: Item) -> Result<Option<Item>, ItemError> {
+        let slot = item.slot().ok_or(ItemError::NotEquipment(item.id))?;
+        if self.items.iter().any(|equipped| equipped.id == item.id) {
+            return Err(ItemError::Duplicate(item.id));
+        }
+        let previous = self.unequip(slot);
+        self.add(item)?;
+        Ok(previous)
+    }
+
+    /// Get the attack profile of the equipped weapon, or the unarmed profile if there is
+    /// no weapon equipped.
+    pub fn weapon(&self) -> Weapon {
+        self.get(EquipmentType::Weapon)
+            .and_then(|item| item.weapon)
+            .unwrap_or_else(Weapon::unarmed)
+    }
+
+    /// Removes and returns the item equipped in the given slot.
+    pub fn unequip(&mut self, slot: EquipmentType) -> Option<Item> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.slot() == Some(slot))?;
+        let item = self.items.remove(index);
+        self.detect_sets();
+        Some(item)
+    }
+
+    /// Iterates over every equipped item.
+    pub fn iter(&self) -> std::slice::Iter<'_, Item> {
+        self.items.iter()
+    }
+
+    /// Get the sum of the stats of every equipped item. This is the bonus the equipment
+    /// gives to the entity wearing it.
+    /// The modifiers of every active set bonus are included.
+    pub fn stats(&self) -> Stats {
+        let mut stats = self
+            .items
+            .iter()
+            .fold(Stats::default(), |total, item| &total + &item.stats);
+        for bonus in self.active_set_bonuses() {
+            for &(name, modifier) in &bonus.modifiers {
+                stats.add_modifier(name, modifier);
+            }
+        }
+        stats
+    }
+
+    /// Get the progress of every set with at least one piece equipped.
+    pub fn sets(&self) -> &[SetProgress] {
+        &self.sets
+    }
+
+    /// Get every set bonus unlocked by the equipped items.
+    pub fn active_set_bonuses(&self) -> impl Iterator<Item = &SetBonus> {
+        self.sets.iter().flat_map(SetProgress::active_bonuses)
+    }
+
+    /// Get the names of the passive abilities granted by active set bonuses.
+    pub fn passives(&self) -> Vec<Cow<'static, str>> {
+        self.active_set_bonuses()
+            .flat_map(|bonus| bonus.passives.iter().cloned())
+            .collect()
+    }
+
+    /// Fails if the item is not equipment, there is no room for it or an item with the
+    /// same id is already equipped.
+    fn check(&self, item: &Item) -> Result<(), ItemError> {
+        if item.slot().is_none() {
+            Err(ItemError::NotEquipment(item.id))
+        } else if self.items.len() >= Self::MAX_ITEMS {
+            Err(ItemError::TooManyEquipment)
+        } else if self.items.iter().any(|equipped| equipped.id == item.id) {
+            Err(ItemError::Duplicate(item.id))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Counts the pieces of every set that are equipped.
+    fn detect_sets(&mut self) {
+        let mut sets: Vec<SetProgress> = Vec::new();
+        for set in self.items.iter().filter_map(|item| item.set.as_ref()) {
+            match sets
+                .iter_mut()
+                .find(|progress| progress.set.name == set.name)
+            {
+                Some(progress) => progress.equipped += 1,
+                None => sets.push(SetProgress {
+                    set: set.clone(),
+                    equipped: 1,
+                }),
+            }
+        }
+        self.sets = sets;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        misc::{ItemKind, ItemSet},
+        stats::{Modifier, StatName},
+        testing,
+    };
+    use std::sync::Arc;
+
+    fn guardian() -> Arc<ItemSet> {
+        ItemSet::new(
+            "Guardian",
+            3,
+            vec![
+                SetBonus {
+                    pieces: 2,
+                    modifiers: vec![(StatName::Defense, Modifier::Plus(5.0))],
+                    passives: vec![],
+                },
+                SetBonus {
+                    pieces: 3,
+                    modifiers: vec![],
+                    passives: vec!["Cover".into()],
+                },
+            ],
+        )
+    }
+
+    fn piece(set: &Arc<ItemSet>, slot: EquipmentType) -> Item {
+        testing::item(ItemKind::Equipment(slot), slot as usize)
+            .set(Some(set.clone()))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn set_bonuses_unlock_with_the_pieces_worn() {
+        let set = guardian();
+        let mut equipment = Equipment::new(vec![piece(&set, EquipmentType::Head)]).unwrap();
+        assert_eq!(equipment.sets()[0].equipped, 1);
+        assert_eq!(equipment.active_set_bonuses().count(), 0);
+        assert_eq!(equipment.sets()[0].next_bonus().map(|b| b.pieces), Some(2));
+
+        equipment.equip(piece(&set, EquipmentType::Armour)).unwrap();
+        assert_eq!(equipment.stats().value(StatName::Defense), 5.0);
+        assert!(equipment.passives().is_empty());
+
+        equipment.equip(piece(&set, EquipmentType::Legs)).unwrap();
+        assert!(equipment.sets()[0].is_complete());
+        assert_eq!(equipment.passives(), vec!["Cover"]);
+    }
+
+    #[test]
+    fn unequipping_a_piece_removes_its_bonus() {
+        let set = guardian();
+        let mut equipment = Equipment::new(vec![
+            piece(&set, EquipmentType::Head),
+            piece(&set, EquipmentType::Armour),
+        ])
+        .unwrap();
+        equipment.unequip(EquipmentType::Head);
+        assert_eq!(equipment.stats().value(StatName::Defense), 0.0);
+        assert!(!equipment.sets()[0].is_active());
+    }
+
+    #[test]
+    fn only_distinct_equipment_fits() {
+        let helmet = testing::equipment(EquipmentType::Head, &[]);
+        let potion = testing::item(ItemKind::UsableEverywhere, 1)
+            .build()
+            .unwrap();
+        assert_eq!(
+            Equipment::new(vec![potion.clone()]),
+            Err(ItemError::NotEquipment(potion.id))
+        );
+        assert_eq!(
+            Equipment::new(vec![helmet.clone(), helmet.clone()]),
+            Err(ItemError::Duplicate(helmet.id))
+        );
+
+        let mut equipment = Equipment::new(vec![helmet.clone()]).unwrap();
+        assert_eq!(
+            equipment.equip(potion.clone()),
+            Err(ItemError::NotEquipment(potion.id))
+        );
+        assert_eq!(
+            equipment.equip(helmet.clone()),
+            Err(ItemError::Duplicate(helmet.id))
+        );
+        assert_eq!(equipment.get(EquipmentType::Head), Some(&helmet));
+    }
+
+    #[test]
+    fn no_more_than_six_pieces_fit() {
+        let mut equipment = Equipment::new(vec![]).unwrap();
+        for _ in 0..Equipment::MAX_ITEMS {
+            let ring = testing::equipment(EquipmentType::Accessory, &[]);
+            equipment.add(ring).unwrap();
+        }
+        let ring = testing::equipment(EquipmentType::Accessory, &[]);
+        assert_eq!(equipment.add(ring), Err(ItemError::TooManyEquipment));
     }
 }
@@ -0,0 +1,121 @@
+use super::{EquipmentType, Item, ItemError};
+use crate::ids::{ItemId, TemplateId};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Represents the items carried by the party that are not equipped on anyone.
+pub struct Inventory(Vec<Item>);
+
+impl Inventory {
+    /// Create a new instance of an inventory given a list of items. Every item must
+    /// have a different id.
+    pub fn new(items: Vec<Item>) -> Result<Self, ItemError> {
+        let mut inventory = Self::default();
+        inventory.extend(items)?;
+        Ok(inventory)
+    }
+
+    /// Adds an item to the inventory, given that no item in it has the same id.
+    pub fn add(&mut self, item: Item) -> Result<(), ItemError> {
+        if self.get(item.id).is_some() {
+            return Err(ItemError::Duplicate(item.id));
+        }
+        self.0.push(item);
+        Ok(())
+    }
+
+    /// Adds every item to the inventory, given that they all have different ids that
+    /// are not in it yet. Nothing is added if one of them fails.
+    pub fn extend(&mut self, items: Vec<Item>) -> Result<(), ItemError> {
+        for (index, item) in items.iter().enumerate() {
+            if self.get(item.id).is_some() || items[..index].iter().any(|i| i.id == item.id) {
+                return Err(ItemError::Duplicate(item.id));
+            }
+        }
+        self.0.extend(items);
+        Ok(())
+    }
+
+    /// Removes and returns the item with the given id.
+    pub fn remove_by_id(&mut self, id: ItemId) -> Option<Item> {
+        let index = self.0.iter().position(|item| item.id == id)?;
+        Some(self.0.remove(index))
+    }
+
+    /// Get the item with the given id.
+    pub fn get(&self, id: ItemId) -> Option<&Item> {
+        self.0.iter().find(|item| item.id == id)
+    }
+
+    /// Counts the items of the given template.
+    pub fn count(&self, template: TemplateId) -> usize {
+        self.0
+            .iter()
+            .filter(|item| item.template == template)
+            .count()
+    }
+
+    /// Removes and returns the given number of items of the template, or nothing if
+    /// there are fewer than that.
+    pub fn take(&mut self, template: TemplateId, quantity: usize) -> Option<Vec<Item>> {
+        if self.count(template) < quantity {
+            return None;
+        }
+        let mut taken = Vec::with_capacity(quantity);
+        while taken.len() < quantity {
+            let index = self.0.iter().position(|item| item.template == template)?;
+            taken.push(self.0.remove(index));
+        }
+        Some(taken)
+    }
+
+    /// Iterates over every item in the inventory.
+    pub fn iter(&self) -> std::slice::Iter<'_, Item> {
+        self.0.iter()
+    }
+
+    /// Iterates over every piece of equipment that goes into the given slot.
+    pub fn equipment(&self, slot: EquipmentType) -> impl Iterator<Item = &Item> {
+        self.0.iter().filter(move |item| item.slot() == Some(slot))
+    }
+
+    /// The number of items in the inventory.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the inventory holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::ItemKind, testing};
+
+    #[test]
+    fn items_are_only_carried_once() {
+        let potion = testing::item(ItemKind::UsableEverywhere, 1)
+            .build()
+            .unwrap();
+        let copy = potion.instantiate();
+        assert_eq!(
+            Inventory::new(vec![potion.clone(), potion.clone()]),
+            Err(ItemError::Duplicate(potion.id))
+        );
+
+        let mut inventory = Inventory::new(vec![potion.clone()]).unwrap();
+        assert_eq!(
+            inventory.add(potion.clone()),
+            Err(ItemError::Duplicate(potion.id))
+        );
+        assert_eq!(
+            inventory.extend(vec![copy.clone(), potion.clone()]),
+            Err(ItemError::Duplicate(potion.id))
+        );
+        assert_eq!(inventory.len(), 1);
+        inventory.extend(vec![copy]).unwrap();
+        assert_eq!(inventory.count(potion.template), 2);
+    }
+}
@@ -1,18 +1,151 @@
-use super::ItemKind;
-use crate::{entities::Entity, stats::Stats};
+use super::{Affix, EquipmentType, ItemKind, ItemSet, Rarity, Restriction, Weapon};
+use crate::{
+    effects::{Effect, Target},
+    ids::{ItemId, TemplateId},
+    stats::Stats,
+};
+use std::{borrow::Cow, sync::Arc};
 
 #[derive(Builder, Debug, Clone, PartialEq)]
-#[builder(pattern = "owned")]
+#[builder(pattern = "owned", build_fn(validate = "Self::validate"))]
 /// Represents an Item in the game. An item can have a name, an id,
-/// a kind, a restriction, description, and stats.
+/// a template, a kind, a restriction, description, and stats.
+/// id identifies this instance of the item, while template identifies
+/// the kind of item it is. Copies of an item should be made with
+/// Item::instantiate so they each get their own id.
+/// key is used to look up the name and description in other languages,
+/// falling back to name and desc if it is empty or missing.
 /// The kind determines when and how the item can be used.
-/// restriction determines who can use it.
+/// restrictions determine who can use it.
 /// stats determine the effect that this item has on the stats.
+/// weapon holds the attack profile of items equipped as a weapon. Every weapon
+/// must have one, and no other item may.
+/// set holds the equipment set this item is a piece of.
+/// effects determine what happens to the targets when the item is used.
+/// rarity determines how often the item drops.
+/// value is the price the item is bought for in shops, before any modifiers.
+/// upgrade is how many times the equipment has been upgraded, shown as +N.
+/// base_stats are the stats the equipment had at +0, kept once it is upgraded.
+/// affixes are the random prefixes and suffixes rolled onto generated equipment.
 pub struct Item {
-    pub name: &'static str,
-    pub id: usize,
+    #[builder(setter(into))]
+    pub name: Cow<'static, str>,
+    #[builder(default = "ItemId::generate()")]
+    pub id: ItemId,
+    pub template: TemplateId,
     pub kind: ItemKind,
-    pub restriction: Option<Vec<Entity>>,
-    pub desc: &'static str,
+    #[builder(default)]
+    pub restrictions: Vec<Restriction>,
+    #[builder(setter(into))]
+    pub desc: Cow<'static, str>,
+    #[builder(default, setter(into))]
+    pub key: Cow<'static, str>,
     pub stats: Stats,
+    #[builder(default)]
+    pub weapon: Option<Weapon>,
+    #[builder(default)]
+    pub set: Option<Arc<ItemSet>>,
+    #[builder(default)]
+    pub effects: Vec<Effect>,
+    #[builder(default)]
+    pub target: Target,
+    #[builder(default)]
+    pub rarity: Rarity,
+    #[builder(default)]
+    pub value: usize,
+    #[builder(default)]
+    pub upgrade: u8,
+    #[builder(default)]
+    pub base_stats: Option<Stats>,
+    #[builder(default)]
+    pub affixes: Vec<Affix>,
+}
+
+impl Item {
+    /// Creates a copy of this item with an id of its own.
+    pub fn instantiate(&self) -> Self {
+        Self {
+            id: ItemId::generate(),
+            ..self.clone()
+        }
+    }
+
+    /// Get the localization key of the name of this item: item.<key>.name. Items
+    /// without a key have none.
+    pub fn name_key(&self) -> Option<String> {
+        (!self.key.is_empty()).then(|| format!("item.{}.name", self.key))
+    }
+
+    /// Get the localization key of the description of this item: item.<key>.desc.
+    /// Items without a key have none.
+    pub fn desc_key(&self) -> Option<String> {
+        (!self.key.is_empty()).then(|| format!("item.{}.desc", self.key))
+    }
+
+    /// Get the equipment slot this item goes into, if it is equipment.
+    pub fn slot(&self) -> Option<EquipmentType> {
+        match self.kind {
+            ItemKind::Equipment(slot) => Some(slot),
+            _ => None,
+        }
+    }
+}
+
+impl ItemBuilder {
+    /// Checks that the item has an attack profile if and only if it is a weapon.
+    fn validate(&self) -> Result<(), String> {
+        let is_weapon = self.kind == Some(ItemKind::Equipment(EquipmentType::Weapon));
+        let has_profile = matches!(self.weapon, Some(Some(_)));
+        match (is_weapon, has_profile) {
+            (true, false) => Err("Weapons must have an attack profile".to_string()),
+            (false, true) => Err("Only weapons can have an attack profile".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::WeaponCategory, testing};
+
+    #[test]
+    fn weapons_need_an_attack_profile() {
+        let weapon = ItemKind::Equipment(EquipmentType::Weapon);
+        assert!(testing::item(weapon, 1).build().is_ok());
+        assert!(testing::item(weapon, 1).weapon(None).build().is_err());
+    }
+
+    #[test]
+    fn only_weapons_have_an_attack_profile() {
+        let profile = Some(Weapon::new(WeaponCategory::Axe, 5));
+        let armour = ItemKind::Equipment(EquipmentType::Armour);
+        assert!(testing::item(armour, 1).build().is_ok());
+        assert!(testing::item(armour, 1).weapon(profile).build().is_err());
+        assert!(testing::item(ItemKind::UsableEverywhere, 1)
+            .weapon(profile)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn instances_get_their_own_id() {
+        let item = testing::item(ItemKind::KeyItem, 1).build().unwrap();
+        let copy = item.instantiate();
+        assert_ne!(copy.id, item.id);
+        assert_eq!(copy.template, item.template);
+    }
+
+    #[test]
+    fn names_and_descriptions_can_be_loaded() {
+        let line = "Ether|Restores 10 SP";
+        let (name, desc) = line.split_once('|').unwrap();
+        let item = testing::item(ItemKind::UsableEverywhere, 1)
+            .name(name.to_string())
+            .desc(desc.to_string())
+            .build()
+            .unwrap();
+        assert_eq!(item.name, "Ether");
+        assert_eq!(item.desc, "Restores 10 SP");
+    }
 }
@@ -0,0 +1,28 @@
+use crate::ids::ItemId;
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+/// Represents the reasons an item cannot be put into an inventory or equipment. A
+/// rejected item leaves them as they were.
+pub enum ItemError {
+    /// The item with this id is not equipment.
+    NotEquipment(ItemId),
+    /// There would be more than six pieces of equipment on an entity.
+    TooManyEquipment,
+    /// There is already an item with this id.
+    Duplicate(ItemId),
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEquipment(id) => write!(f, "Item {} is not equipment", id),
+            Self::TooManyEquipment => {
+                write!(f, "There can be no more than six equipment on an entity.")
+            }
+            Self::Duplicate(id) => write!(f, "There is already an item with id {}", id),
+        }
+    }
+}
+
+impl Error for ItemError {}
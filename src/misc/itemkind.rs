@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use crate::effects::Context;
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 /// Represents the possible kinds of items there can be in the game.
 pub enum ItemKind {
@@ -19,12 +21,40 @@ pub enum ItemKind {
     Equipment(EquipmentType),
 }
 
+impl ItemKind {
+    /// Whether items of this kind can be used in the given context. Key items and
+    /// equipment can never be used.
+    pub fn usable_in(&self, context: Context) -> bool {
+        match self {
+            Self::UsableInBattle => context == Context::Battle,
+            Self::UsableInField => context == Context::Field,
+            Self::UsableEverywhere => true,
+            Self::KeyItem | Self::Equipment(_) => false,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 /// Represents the types of equipment an entity can have. They can only have one of each.
 pub enum EquipmentType {
+    /// Weapons decide how their wielder attacks. Their attack profile is stored in
+    /// Item.weapon.
+    Weapon,
     Accessory,
     Armour,
     Legs,
     Feet,
     Head,
 }
+
+impl EquipmentType {
+    /// Every equipment slot an entity has.
+    pub const ALL: [EquipmentType; 6] = [
+        Self::Weapon,
+        Self::Accessory,
+        Self::Armour,
+        Self::Legs,
+        Self::Feet,
+        Self::Head,
+    ];
+}
@@ -0,0 +1,79 @@
+use crate::stats::{Modifier, StatName};
+use std::{borrow::Cow, sync::Arc};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents the bonus granted by wearing a number of pieces of the same set.
+pub struct SetBonus {
+    /// How many pieces of the set must be worn for this bonus to be active.
+    pub pieces: usize,
+    /// The modifiers applied to the stats of the wearer while the bonus is active.
+    pub modifiers: Vec<(StatName, Modifier)>,
+    /// The names of the passive abilities granted while the bonus is active.
+    pub passives: Vec<Cow<'static, str>>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a named set of equipment spread across several slots. Every piece of
+/// the set holds a shared reference to it in Item.set.
+pub struct ItemSet {
+    /// The ingame name of the set. Pieces belong to the same set if the names match.
+    pub name: Cow<'static, str>,
+    /// How many pieces there are in the set.
+    pub pieces: usize,
+    /// The bonuses of the set, each unlocked by wearing a number of pieces.
+    pub bonuses: Vec<SetBonus>,
+}
+
+impl ItemSet {
+    /// Create a new item set given its name, its number of pieces and its bonuses.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        pieces: usize,
+        bonuses: Vec<SetBonus>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            name: name.into(),
+            pieces,
+            bonuses,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents how many pieces of a set an entity is wearing. Used by the UI to show
+/// partial progress towards the bonuses of a set.
+pub struct SetProgress {
+    /// The set being worn.
+    pub set: Arc<ItemSet>,
+    /// How many pieces of the set are equipped.
+    pub equipped: usize,
+}
+
+impl SetProgress {
+    /// Get every bonus unlocked by the pieces currently equipped.
+    pub fn active_bonuses(&self) -> impl Iterator<Item = &SetBonus> {
+        self.set
+            .bonuses
+            .iter()
+            .filter(move |bonus| bonus.pieces <= self.equipped)
+    }
+
+    /// Get the bonus that will be unlocked next, if there is one.
+    pub fn next_bonus(&self) -> Option<&SetBonus> {
+        self.set
+            .bonuses
+            .iter()
+            .filter(|bonus| bonus.pieces > self.equipped)
+            .min_by_key(|bonus| bonus.pieces)
+    }
+
+    /// Whether at least one bonus of the set is active.
+    pub fn is_active(&self) -> bool {
+        self.active_bonuses().next().is_some()
+    }
+
+    /// Whether every piece of the set is equipped.
+    pub fn is_complete(&self) -> bool {
+        self.equipped >= self.set.pieces
+    }
+}
@@ -1,7 +1,25 @@
+mod advisor;
+mod affix;
+mod element;
 mod equipment;
+mod inventory;
 mod item;
+mod itemerror;
 mod itemkind;
+mod itemset;
+mod rarity;
+mod restriction;
+mod weapon;
 
+pub use advisor::{EquipmentAdvisor, StatWeights, Suggestion};
+pub use affix::{Affix, AffixKind, AffixPool};
+pub use element::Element;
 pub use equipment::Equipment;
-pub use item::Item;
+pub use inventory::Inventory;
+pub use item::{Item, ItemBuilder};
+pub use itemerror::ItemError;
 pub use itemkind::{EquipmentType, ItemKind};
+pub use itemset::{ItemSet, SetBonus, SetProgress};
+pub use rarity::Rarity;
+pub use restriction::{Restriction, UseDenied};
+pub use weapon::{Weapon, WeaponCategory};
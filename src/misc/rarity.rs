@@ -0,0 +1,52 @@
+use crate::random;
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents how rare an item is. Rarer items drop less often.
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    /// Every rarity, from the most common to the rarest.
+    pub const ALL: [Rarity; 5] = [
+        Self::Common,
+        Self::Uncommon,
+        Self::Rare,
+        Self::Epic,
+        Self::Legendary,
+    ];
+
+    /// Rolls a random rarity, weighted by the drop weight of each.
+    pub fn roll() -> Self {
+        let weights = Self::ALL.map(|rarity| rarity.drop_weight());
+        random::weighted(&weights).map_or(Self::Common, |index| Self::ALL[index])
+    }
+
+    /// Get the number of random affixes generated equipment of this rarity has.
+    pub fn affixes(&self) -> usize {
+        match self {
+            Self::Common => 0,
+            Self::Uncommon => 1,
+            Self::Rare => 2,
+            Self::Epic => 3,
+            Self::Legendary => 4,
+        }
+    }
+
+    /// Get the weight used for drops of this rarity when a loot table does not give
+    /// one explicitly.
+    pub fn drop_weight(&self) -> u32 {
+        match self {
+            Self::Common => 100,
+            Self::Uncommon => 40,
+            Self::Rare => 12,
+            Self::Epic => 3,
+            Self::Legendary => 1,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::ItemError;
+use crate::{entities::Entity, ids::EntityId, stats::StatName};
+use std::{borrow::Cow, error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+/// Represents a rule that decides who can use an item. An item can have several
+/// restrictions, and an entity must satisfy all of them to use it.
+pub enum Restriction {
+    /// Only the entities with these ids can use the item.
+    Entities(Vec<EntityId>),
+    /// Only entities of the class with this name can use the item.
+    Class(Cow<'static, str>),
+    /// Only entities of at least this level can use the item.
+    MinLevel(u8),
+    /// Only entities whose stat is at least the given value can use the item. The
+    /// maximum of depletable stats is compared, so damage does not matter.
+    Stat(StatName, usize),
+    /// Only entities with this tag can use the item.
+    Tag(Cow<'static, str>),
+}
+
+impl Restriction {
+    /// Checks whether an entity satisfies this restriction, returning the reason if
+    /// it does not.
+    pub fn check(&self, entity: &Entity) -> Result<(), UseDenied> {
+        match self {
+            Self::Entities(ids) if !ids.contains(&entity.id()) => Err(UseDenied::NotAllowed),
+            Self::Class(class) if entity.class.as_ref().map(|c| &c.name) != Some(class) => {
+                Err(UseDenied::WrongClass(class.clone()))
+            }
+            Self::MinLevel(level) if entity.level_data.level() < *level => {
+                Err(UseDenied::LevelTooLow(*level))
+            }
+            Self::Stat(name, value) if entity.stats.get(*name).map_or(0, |s| s.max()) < *value => {
+                Err(UseDenied::StatTooLow(*name, *value))
+            }
+            Self::Tag(tag) if !entity.tags.contains(tag) => Err(UseDenied::MissingTag(tag.clone())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+/// Represents the reason an entity is not allowed to use an item.
+pub enum UseDenied {
+    /// The item is reserved for other entities.
+    NotAllowed,
+    /// The item requires the class with this name.
+    WrongClass(Cow<'static, str>),
+    /// The item requires at least this level.
+    LevelTooLow(u8),
+    /// The item requires at least this value of the stat.
+    StatTooLow(StatName, usize),
+    /// The item requires this tag.
+    MissingTag(Cow<'static, str>),
+    /// The class with this name is not allowed to equip the item.
+    CannotEquip(Cow<'static, str>),
+    /// The item cannot be put into the equipment at all.
+    Invalid(ItemError),
+    /// The equipment of the entity cannot be changed.
+    EquipmentLocked,
+}
+
+impl fmt::Display for UseDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAllowed => write!(f, "This item is reserved for someone else"),
+            Self::WrongClass(class) => write!(f, "Only a {} can use this item", class),
+            Self::LevelTooLow(level) => write!(f, "Requires level {}", level),
+            Self::StatTooLow(name, value) => write!(f, "Requires {} {}", value, name),
+            Self::MissingTag(tag) => write!(f, "Requires {}", tag),
+            Self::CannotEquip(class) => write!(f, "A {} cannot equip this item", class),
+            Self::Invalid(error) => write!(f, "{}", error),
+            Self::EquipmentLocked => write!(f, "This equipment cannot be changed"),
+        }
+    }
+}
+
+impl Error for UseDenied {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        levels::LevelData,
+        misc::{EquipmentType, ItemKind},
+        testing,
+    };
+
+    fn restricted(restrictions: Vec<Restriction>) -> crate::misc::Item {
+        testing::item(ItemKind::UsableEverywhere, 1)
+            .restrictions(restrictions)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn every_restriction_must_be_met() {
+        let entity = testing::entity("Hero")
+            .level_data(LevelData::new(5, 0))
+            .tags(vec!["Human".into()])
+            .build()
+            .unwrap();
+        let item = restricted(vec![
+            Restriction::MinLevel(5),
+            Restriction::Tag("Human".into()),
+            Restriction::Stat(StatName::Attack, 10),
+            Restriction::Entities(vec![entity.id()]),
+        ]);
+        assert_eq!(entity.can_use(&item), Ok(()));
+    }
+
+    #[test]
+    fn the_first_failed_restriction_is_reported() {
+        let entity = testing::entity("Hero").build().unwrap();
+        let cases = [
+            (Restriction::MinLevel(3), UseDenied::LevelTooLow(3)),
+            (
+                Restriction::Tag("Elf".into()),
+                UseDenied::MissingTag("Elf".into()),
+            ),
+            (
+                Restriction::Stat(StatName::Attack, 11),
+                UseDenied::StatTooLow(StatName::Attack, 11),
+            ),
+            (
+                Restriction::Entities(vec![EntityId::generate()]),
+                UseDenied::NotAllowed,
+            ),
+            (
+                Restriction::Class("Mage".into()),
+                UseDenied::WrongClass("Mage".into()),
+            ),
+        ];
+        for (restriction, denied) in cases {
+            let item = restricted(vec![Restriction::MinLevel(1), restriction]);
+            assert_eq!(entity.can_use(&item), Err(denied));
+        }
+    }
+
+    #[test]
+    fn classes_limit_what_can_be_equipped() {
+        let mage = testing::class("Mage")
+            .equipment(Some(vec![EquipmentType::Head]))
+            .build()
+            .unwrap();
+        let entity = testing::entity("Hero").with_class(mage).build().unwrap();
+        let helmet = testing::equipment(EquipmentType::Head, &[]);
+        let armour = testing::equipment(EquipmentType::Armour, &[]);
+        assert_eq!(entity.can_equip(&helmet), Ok(()));
+        assert_eq!(
+            entity.can_equip(&armour),
+            Err(UseDenied::CannotEquip("Mage".into()))
+        );
+        let item = restricted(vec![Restriction::Class("Mage".into())]);
+        assert_eq!(entity.can_use(&item), Ok(()));
+    }
+
+    #[test]
+    fn stat_restrictions_ignore_damage() {
+        let mut entity = testing::entity("Hero").build().unwrap();
+        let item = restricted(vec![Restriction::Stat(StatName::HealthPoints, 100)]);
+        entity.stats[StatName::HealthPoints].damage(99);
+        assert_eq!(entity.can_use(&item), Ok(()));
+    }
+}
@@ -0,0 +1,68 @@
+use super::Element;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents the kinds of weapons there are. The category decides which stats a
+/// weapon attacks with and whether it is used at range.
+pub enum WeaponCategory {
+    Sword,
+    Axe,
+    Spear,
+    Dagger,
+    /// Bows attack from range.
+    Bow,
+    /// Staves attack with SpecialAttack against SpecialDefense, from range.
+    Staff,
+    /// Used when an entity has no weapon equipped.
+    Unarmed,
+}
+
+impl WeaponCategory {
+    /// Whether weapons of this category attack from range instead of in melee.
+    pub fn is_ranged(&self) -> bool {
+        matches!(self, Self::Bow | Self::Staff)
+    }
+
+    /// Whether weapons of this category use SpecialAttack and SpecialDefense instead
+    /// of Attack and Defense.
+    pub fn is_special(&self) -> bool {
+        matches!(self, Self::Staff)
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents the attack profile of a weapon. It is consumed by the damage
+/// calculation whenever its wielder performs a basic attack.
+pub struct Weapon {
+    /// The kind of weapon.
+    pub category: WeaponCategory,
+    /// Added to the attacking stat of the wielder.
+    pub power: usize,
+    /// The element of every hit.
+    pub element: Element,
+    /// How many times the weapon hits per attack. Each hit is rolled separately.
+    pub hits: u8,
+    /// Added to the base chance of a critical hit. Between 0 and 1.
+    pub crit_bonus: f64,
+    /// The chance of hitting before the evasion of the target is taken into account.
+    /// Between 0 and 1.
+    pub accuracy: f64,
+}
+
+impl Weapon {
+    /// Create a new weapon with a single hit, no element, no crit bonus and 95% accuracy.
+    pub fn new(category: WeaponCategory, power: usize) -> Self {
+        Self {
+            category,
+            power,
+            element: Element::Neutral,
+            hits: 1,
+            crit_bonus: 0.0,
+            accuracy: 0.95,
+        }
+    }
+
+    /// The profile used when an entity attacks without a weapon.
+    pub fn unarmed() -> Self {
+        Self::new(WeaponCategory::Unarmed, 0)
+    }
+}
@@ -0,0 +1,3 @@
+mod rng;
+
+pub use rng::{chance, is_dice, range, roll, seed, weighted};
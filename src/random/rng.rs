@@ -0,0 +1,65 @@
+use bracket_random::prelude::{parse_dice_string, RandomNumberGenerator};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// The global static instance of the random number generator. This generator from bracket-lib has
+/// the features of using dice notation for its number generation, which make it perfect for stat growth.
+static RNG: Lazy<Mutex<RandomNumberGenerator>> =
+    Lazy::new(|| Mutex::new(RandomNumberGenerator::new()));
+
+/// Reseeds the global random number generator. Every roll made afterwards is
+/// reproducible, which is useful for replays and for testing loot.
+pub fn seed(seed: u64) {
+    // Note: If something explodes, it's probably the Mutex.
+    *RNG.lock().unwrap() = RandomNumberGenerator::seeded(seed);
+}
+
+/// Returns true if the string is in dice notation, such as 2d6+3, or is a plain number.
+pub fn is_dice(dice: &str) -> bool {
+    dice.trim().parse::<i32>().is_ok() || parse_dice_string(dice).is_ok()
+}
+
+/// Rolls a string in dice notation, such as 2d6+3. A plain number such as 5 is
+/// returned as is. Panics if the string is not in dice notation, so dice that come
+/// from data should be checked with is_dice first.
+pub fn roll(dice: &str) -> i32 {
+    if let Ok(value) = dice.trim().parse() {
+        return value;
+    }
+    match RNG.lock().unwrap().roll_str(dice) {
+        Ok(value) => value,
+        Err(_) => panic!("Invalid dice notation {:?}", dice),
+    }
+}
+
+/// Generates a random number between min (inclusive) and max (exclusive).
+pub fn range(min: i32, max: i32) -> i32 {
+    if min >= max {
+        min
+    } else {
+        RNG.lock().unwrap().range(min, max)
+    }
+}
+
+/// Returns true with the given probability, which is clamped between 0 and 1.
+pub fn chance(probability: f64) -> bool {
+    RNG.lock().unwrap().rand::<f64>() < probability.clamp(0.0, 1.0)
+}
+
+/// Picks a random index from a list of weights, where the chance of each index being
+/// picked is proportional to its weight. Returns None if every weight is zero.
+pub fn weighted(weights: &[u32]) -> Option<usize> {
+    let total = weights.iter().map(|&weight| weight as u64).sum::<u64>();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = RNG.lock().unwrap().next_u64() % total;
+    weights.iter().position(|&weight| {
+        if roll < weight as u64 {
+            true
+        } else {
+            roll -= weight as u64;
+            false
+        }
+    })
+}
@@ -0,0 +1,10 @@
+mod pricemodifier;
+#[allow(clippy::module_inception)]
+mod shop;
+mod shoperror;
+mod stock;
+
+pub use pricemodifier::PriceModifier;
+pub use shop::{Shop, ShopBuilder};
+pub use shoperror::ShopError;
+pub use stock::{Restock, Stock};
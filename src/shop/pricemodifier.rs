@@ -0,0 +1,44 @@
+use crate::{entities::Party, ids::TemplateId, stats::StatName};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+/// Represents a discount a shop gives on the items it sells. Every discount is a
+/// fraction of the price, and the discounts that apply are added together.
+pub enum PriceModifier {
+    /// A discount for every point of Friendship of the party leader, up to max.
+    Friendship { per_point: f64, max: f64 },
+    /// A discount if the party carries or has equipped an item of the given template,
+    /// such as a membership card.
+    Item { template: TemplateId, discount: f64 },
+    /// A discount that always applies, such as during a sale.
+    Flat(f64),
+}
+
+impl PriceModifier {
+    /// Get the discount this modifier gives to the party.
+    pub fn discount(&self, party: &Party) -> f64 {
+        match *self {
+            Self::Friendship { per_point, max } => party
+                .leader()
+                .and_then(|id| party.entity(id))
+                .map_or(0.0, |leader| {
+                    (leader.stats.value(StatName::Friendship) * per_point).min(max)
+                }),
+            Self::Item { template, discount } => {
+                let carried = party
+                    .inventory()
+                    .iter()
+                    .any(|item| item.template == template);
+                let equipped = party
+                    .members()
+                    .flat_map(|member| member.entity.equipment().iter())
+                    .any(|item| item.template == template);
+                if carried || equipped {
+                    discount
+                } else {
+                    0.0
+                }
+            }
+            Self::Flat(discount) => discount,
+        }
+    }
+}
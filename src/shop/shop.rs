@@ -0,0 +1,213 @@
+use super::{PriceModifier, ShopError, Stock};
+use crate::{
+    entities::Party,
+    ids::{ItemId, TemplateId},
+    misc::{Item, ItemKind},
+};
+
+#[derive(Builder, Debug, Clone, PartialEq)]
+#[builder(pattern = "owned")]
+/// Represents a shop the party can buy items from and sell items to. Items are bought
+/// for their value times the markup, less the discounts of the modifiers, and sold for
+/// their value times the sell ratio. Key items cannot be sold.
+pub struct Shop {
+    /// The items the shop sells.
+    pub stock: Vec<Stock>,
+    /// What the value of an item is multiplied by to get its buying price.
+    #[builder(default = "1.0")]
+    pub markup: f64,
+    /// The fraction of the value of an item the shop pays for it.
+    #[builder(default = "0.5")]
+    pub sell_ratio: f64,
+    /// The discounts the shop gives on the items it sells.
+    #[builder(default)]
+    pub modifiers: Vec<PriceModifier>,
+}
+
+impl Shop {
+    /// The largest discount a shop can give, no matter how many modifiers apply.
+    pub const MAX_DISCOUNT: f64 = 0.9;
+
+    /// Get the stock of items of the given template.
+    pub fn get(&self, template: TemplateId) -> Option<&Stock> {
+        self.stock
+            .iter()
+            .find(|stock| stock.item.template == template)
+    }
+
+    /// Get the fraction taken off the buying price for the party.
+    pub fn discount(&self, party: &Party) -> f64 {
+        self.modifiers
+            .iter()
+            .map(|modifier| modifier.discount(party))
+            .sum::<f64>()
+            .clamp(0.0, Self::MAX_DISCOUNT)
+    }
+
+    /// Get the price the party pays for one of the item, rounded up.
+    pub fn buy_price(&self, item: &Item, party: &Party) -> usize {
+        (item.value as f64 * self.markup * (1.0 - self.discount(party))).ceil() as usize
+    }
+
+    /// Get the gold the shop pays for the item, rounded down, or None if it cannot
+    /// be sold.
+    pub fn sell_price(&self, item: &Item) -> Option<usize> {
+        match item.kind {
+            ItemKind::KeyItem => None,
+            _ => Some((item.value as f64 * self.sell_ratio).floor() as usize),
+        }
+    }
+
+    /// Buys the given quantity of items of the template, putting them in the inventory
+    /// of the party. Returns the ids of the new items. Nothing happens if the shop does
+    /// not have enough of the item or the party cannot afford them.
+    pub fn buy(
+        &mut self,
+        party: &mut Party,
+        template: TemplateId,
+        quantity: usize,
+    ) -> Result<Vec<ItemId>, ShopError> {
+        let index = self
+            .stock
+            .iter()
+            .position(|stock| stock.item.template == template)
+            .ok_or(ShopError::NotSold(template))?;
+        let price = self
+            .buy_price(&self.stock[index].item, party)
+            .checked_mul(quantity)
+            .ok_or(ShopError::PriceOverflow(template))?;
+        let stock = &mut self.stock[index];
+        if stock.quantity.is_some_and(|left| left < quantity) {
+            return Err(ShopError::OutOfStock(template));
+        }
+        if party.gold() < price {
+            return Err(ShopError::NotEnoughGold {
+                price,
+                gold: party.gold(),
+            });
+        }
+
+        let items = (0..quantity)
+            .map(|_| stock.item.instantiate())
+            .collect::<Vec<_>>();
+        let ids = items.iter().map(|item| item.id).collect();
+        party
+            .inventory_mut()
+            .extend(items)
+            .map_err(ShopError::Item)?;
+        party.spend_gold(price);
+        if let Some(left) = &mut stock.quantity {
+            *left -= quantity;
+        }
+        Ok(ids)
+    }
+
+    /// Sells the item with the given id from the inventory of the party, returning the
+    /// gold paid for it.
+    pub fn sell(&mut self, party: &mut Party, id: ItemId) -> Result<usize, ShopError> {
+        let item = party.inventory().get(id).ok_or(ShopError::NotFound(id))?;
+        let price = self.sell_price(item).ok_or(ShopError::Unsellable(id))?;
+        party.inventory_mut().remove_by_id(id);
+        party.add_gold(price);
+        Ok(price)
+    }
+
+    /// Refills every limited stock according to its restock rule.
+    pub fn restock(&mut self) {
+        self.stock.iter_mut().for_each(Stock::restock);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shop::Restock, testing};
+
+    fn potion() -> Item {
+        testing::item(ItemKind::UsableEverywhere, 1)
+            .value(10)
+            .build()
+            .unwrap()
+    }
+
+    fn party(gold: usize) -> Party {
+        let mut party = Party::new(vec![testing::entity("Hero").build().unwrap()], vec![]).unwrap();
+        party.add_gold(gold);
+        party
+    }
+
+    #[test]
+    fn buying_spends_gold_and_stock() {
+        let mut shop = ShopBuilder::default()
+            .stock(vec![Stock::limited(potion(), 3, Restock::Full)])
+            .markup(1.5)
+            .modifiers(vec![PriceModifier::Flat(0.2)])
+            .build()
+            .unwrap();
+        let mut party = party(100);
+        let template = TemplateId(1);
+
+        let ids = shop.buy(&mut party, template, 2).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(party.gold(), 76);
+        assert_eq!(party.inventory().count(template), 2);
+        assert_eq!(shop.get(template).unwrap().quantity, Some(1));
+        assert_eq!(
+            shop.buy(&mut party, template, 2),
+            Err(ShopError::OutOfStock(template))
+        );
+        assert_eq!(
+            shop.buy(&mut party, TemplateId(2), 1),
+            Err(ShopError::NotSold(TemplateId(2)))
+        );
+    }
+
+    #[test]
+    fn failed_purchases_change_nothing() {
+        let mut shop = ShopBuilder::default()
+            .stock(vec![Stock::unlimited(potion())])
+            .build()
+            .unwrap();
+        let mut party = party(15);
+        let template = TemplateId(1);
+        assert_eq!(
+            shop.buy(&mut party, template, 2),
+            Err(ShopError::NotEnoughGold {
+                price: 20,
+                gold: 15
+            })
+        );
+        assert_eq!(
+            shop.buy(&mut party, template, usize::MAX),
+            Err(ShopError::PriceOverflow(template))
+        );
+        assert_eq!(party.gold(), 15);
+        assert!(party.inventory().is_empty());
+    }
+
+    #[test]
+    fn key_items_cannot_be_sold() {
+        let mut shop = ShopBuilder::default().stock(vec![]).build().unwrap();
+        let key = testing::item(ItemKind::KeyItem, 2)
+            .value(10)
+            .build()
+            .unwrap();
+        let potion = potion();
+        let ids = (potion.id, key.id);
+        let mut party = party(0);
+        party.inventory_mut().extend(vec![potion, key]).unwrap();
+
+        assert_eq!(shop.sell(&mut party, ids.0), Ok(5));
+        assert_eq!(party.gold(), 5);
+        assert_eq!(
+            shop.sell(&mut party, ids.0),
+            Err(ShopError::NotFound(ids.0))
+        );
+        assert_eq!(
+            shop.sell(&mut party, ids.1),
+            Err(ShopError::Unsellable(ids.1))
+        );
+        assert_eq!(party.inventory().len(), 1);
+    }
+}
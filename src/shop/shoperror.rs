@@ -0,0 +1,48 @@
+use crate::{
+    ids::{ItemId, TemplateId},
+    misc::ItemError,
+};
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Represents the ways buying from or selling to a shop can fail.
+pub enum ShopError {
+    /// The shop does not sell items of the given template.
+    NotSold(TemplateId),
+    /// The shop has fewer of the item left than were asked for.
+    OutOfStock(TemplateId),
+    /// The price of the quantity asked for is too large to be paid.
+    PriceOverflow(TemplateId),
+    /// The party cannot afford the price.
+    NotEnoughGold { price: usize, gold: usize },
+    /// The party does not carry an item with the given id.
+    NotFound(ItemId),
+    /// The item cannot be sold, such as key items.
+    Unsellable(ItemId),
+    /// The items bought could not be put into the inventory.
+    Item(ItemError),
+}
+
+impl fmt::Display for ShopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSold(template) => write!(f, "The shop does not sell item {}", template),
+            Self::OutOfStock(template) => write!(f, "The shop has run out of item {}", template),
+            Self::PriceOverflow(template) => {
+                write!(f, "Too many of item {} were asked for", template)
+            }
+            Self::NotEnoughGold { price, gold } => {
+                write!(
+                    f,
+                    "The price is {} gold, but the party only has {}",
+                    price, gold
+                )
+            }
+            Self::NotFound(id) => write!(f, "The party does not carry item {}", id),
+            Self::Unsellable(id) => write!(f, "Item {} cannot be sold", id),
+            Self::Item(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ShopError {}
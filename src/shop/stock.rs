@@ -0,0 +1,104 @@
+use crate::misc::Item;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Determines how a limited stock is refilled when the shop restocks.
+pub enum Restock {
+    /// The stock is never refilled. Once it is sold out, it is gone for good.
+    Never,
+    /// The stock is refilled up to its capacity.
+    Full,
+    /// The given amount is added to the stock, up to its capacity.
+    By(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an item sold by a shop. Every item bought is a new instance of it.
+pub struct Stock {
+    /// The item being sold.
+    pub item: Item,
+    /// How many are left, or None if the shop never runs out.
+    pub quantity: Option<usize>,
+    /// The most a limited stock can hold after restocking.
+    pub capacity: usize,
+    /// How the stock is refilled when the shop restocks.
+    pub restock: Restock,
+}
+
+impl Stock {
+    /// Create a stock the shop never runs out of.
+    pub fn unlimited(item: Item) -> Self {
+        Self {
+            item,
+            quantity: None,
+            capacity: 0,
+            restock: Restock::Never,
+        }
+    }
+
+    /// Create a stock of the given quantity, which is also its capacity.
+    pub fn limited(item: Item, quantity: usize, restock: Restock) -> Self {
+        Self {
+            item,
+            quantity: Some(quantity),
+            capacity: quantity,
+            restock,
+        }
+    }
+
+    /// Whether there are none of the item left.
+    pub fn is_sold_out(&self) -> bool {
+        self.quantity == Some(0)
+    }
+
+    /// Refills the stock according to its restock rule.
+    pub fn restock(&mut self) {
+        if let Some(quantity) = &mut self.quantity {
+            *quantity = match self.restock {
+                Restock::Never => *quantity,
+                Restock::Full => (*quantity).max(self.capacity),
+                Restock::By(amount) => quantity
+                    .saturating_add(amount)
+                    .min(self.capacity)
+                    .max(*quantity),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{misc::ItemKind, testing};
+
+    fn stock(quantity: usize, restock: Restock) -> Stock {
+        let item = testing::item(ItemKind::UsableEverywhere, 1)
+            .build()
+            .unwrap();
+        Stock {
+            quantity: Some(quantity),
+            ..Stock::limited(item, 5, restock)
+        }
+    }
+
+    #[test]
+    fn restocking_never_goes_past_capacity() {
+        let mut partial = stock(1, Restock::By(3));
+        partial.restock();
+        assert_eq!(partial.quantity, Some(4));
+        partial.restock();
+        assert_eq!(partial.quantity, Some(5));
+
+        let mut full = stock(0, Restock::Full);
+        assert!(full.is_sold_out());
+        full.restock();
+        assert_eq!(full.quantity, Some(5));
+
+        let mut huge = stock(1, Restock::By(usize::MAX));
+        huge.restock();
+        assert_eq!(huge.quantity, Some(5));
+
+        let mut never = stock(0, Restock::Never);
+        never.restock();
+        assert!(never.is_sold_out());
+    }
+}
@@ -0,0 +1,7 @@
+mod skill;
+mod skillevent;
+mod skillset;
+
+pub use skill::Skill;
+pub use skillevent::SkillEvent;
+pub use skillset::SkillSet;
@@ -0,0 +1,33 @@
+use crate::effects::{Effect, Target};
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a skill an entity can use in or outside battle, such as Fire or Cure.
+/// The effects of a skill are carried out by the same interpreter as those of items.
+pub struct Skill {
+    /// The ingame name of the skill. Skills with the same name are the same skill.
+    pub name: Cow<'static, str>,
+    /// The SkillPoints spent when using the skill.
+    pub cost: usize,
+    /// Who the skill can be used on.
+    pub target: Target,
+    /// What happens to each target.
+    pub effects: Vec<Effect>,
+}
+
+impl Skill {
+    /// Create a new skill given its name, cost, target and effects.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        cost: usize,
+        target: Target,
+        effects: Vec<Effect>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            cost,
+            target,
+            effects,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::skills::Skill;
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents what happened when an entity tried to learn a skill.
+pub enum SkillEvent {
+    /// The skill with this name was learned.
+    Learned(Cow<'static, str>),
+    /// The skill with this name was already known.
+    AlreadyKnown(Cow<'static, str>),
+    /// Every skill slot is taken. Another skill must be forgotten to make room for
+    /// this one, see SkillSet::replace.
+    MustForget(Skill),
+}
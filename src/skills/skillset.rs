@@ -0,0 +1,120 @@
+use super::{Skill, SkillEvent};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents the skills an entity knows. There can be no more than slots of them.
+pub struct SkillSet {
+    /// The skills known, in the order they were learned.
+    skills: Vec<Skill>,
+    /// The number of skills that can be known at once.
+    slots: usize,
+}
+
+impl Default for SkillSet {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SLOTS)
+    }
+}
+
+impl SkillSet {
+    /// The number of skills that can be known at once unless set otherwise.
+    pub const DEFAULT_SLOTS: usize = 8;
+
+    /// Create a new empty skill set with the given number of slots.
+    pub fn new(slots: usize) -> Self {
+        Self {
+            skills: Vec::new(),
+            slots,
+        }
+    }
+
+    /// Get the number of skills that can be known at once.
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Iterates over the known skills in the order they were learned.
+    pub fn iter(&self) -> std::slice::Iter<'_, Skill> {
+        self.skills.iter()
+    }
+
+    /// Get the known skill with the given name.
+    pub fn get(&self, name: &str) -> Option<&Skill> {
+        self.skills.iter().find(|skill| skill.name == name)
+    }
+
+    /// Teaches a skill if there is a free slot.
+    pub fn learn(&mut self, skill: Skill) -> SkillEvent {
+        if self.get(&skill.name).is_some() {
+            SkillEvent::AlreadyKnown(skill.name)
+        } else if self.skills.len() >= self.slots {
+            SkillEvent::MustForget(skill)
+        } else {
+            let name = skill.name.clone();
+            self.skills.push(skill);
+            SkillEvent::Learned(name)
+        }
+    }
+
+    /// Forgets the skill with the given name. Returns the skill if it was known.
+    pub fn forget(&mut self, name: &str) -> Option<Skill> {
+        let index = self.skills.iter().position(|skill| skill.name == name)?;
+        Some(self.skills.remove(index))
+    }
+
+    /// Forgets a skill to make room for another, keeping its slot. Does nothing and
+    /// returns false if the skill to forget is not known, or the new skill already is.
+    pub fn replace(&mut self, forget: &str, skill: Skill) -> bool {
+        if skill.name != forget && self.get(&skill.name).is_some() {
+            return false;
+        }
+        match self.skills.iter_mut().find(|known| known.name == forget) {
+            Some(known) => {
+                *known = skill;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The number of known skills.
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
+    /// Whether no skill is known.
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &'static str) -> Skill {
+        Skill::new(name, 4, Default::default(), vec![])
+    }
+
+    #[test]
+    fn replacing_keeps_the_slot() {
+        let mut skills = SkillSet::new(2);
+        skills.learn(skill("Fire"));
+        skills.learn(skill("Cure"));
+        assert!(skills.replace("Fire", skill("Ice")));
+        let names = skills.iter().map(|s| s.name.as_ref()).collect::<Vec<_>>();
+        assert_eq!(names, ["Ice", "Cure"]);
+        assert!(!skills.replace("Fire", skill("Bolt")));
+    }
+
+    #[test]
+    fn skills_are_never_known_twice() {
+        let mut skills = SkillSet::new(2);
+        skills.learn(skill("Fire"));
+        skills.learn(skill("Ice"));
+        assert!(!skills.replace("Fire", skill("Ice")));
+        assert!(skills.get("Fire").is_some());
+        assert!(skills.replace("Ice", skill("Ice")));
+        assert_eq!(skills.len(), 2);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::stats::{Formula, StatName};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a secondary stat whose base value is calculated from other stats
+/// instead of being set directly. For depletable stats the formula decides the
+/// maximum value, such as max HP = 50 + Defense * 2.
+pub struct DerivedStat {
+    /// The stat whose value is calculated.
+    pub name: StatName,
+    /// The formula used to calculate the value.
+    pub formula: Formula,
+}
+
+impl DerivedStat {
+    /// Create a new derived stat given its name and formula.
+    pub fn new(name: StatName, formula: Formula) -> Self {
+        Self { name, formula }
+    }
+}
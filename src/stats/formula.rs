@@ -0,0 +1,101 @@
+use crate::stats::{StatName, Stats};
+use std::ops::{Add, Div, Mul, Sub};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Represents a formula that calculates the value of a derived stat from other
+/// stats. Formulas are built from constants and stat values using the usual
+/// arithmetic operators, so crit chance can be written as:
+/// Formula::from(Speed) / 10.0 + Formula::from(StatName::custom("Luck"))
+pub enum Formula {
+    /// A fixed number.
+    Constant(f64),
+    /// The calculated value of a stat, with its modifiers applied. Stats that are
+    /// not present evaluate to zero.
+    Value(StatName),
+    Add(Box<Formula>, Box<Formula>),
+    Sub(Box<Formula>, Box<Formula>),
+    Mul(Box<Formula>, Box<Formula>),
+    /// Division by zero evaluates to zero instead of infinity.
+    Div(Box<Formula>, Box<Formula>),
+}
+
+impl Formula {
+    /// Calculates the result of this formula using the given stats.
+    pub fn evaluate(&self, stats: &Stats) -> f64 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Value(name) => stats.get(*name).map_or(0.0, |stat| stat.value()),
+            Self::Add(lhs, rhs) => lhs.evaluate(stats) + rhs.evaluate(stats),
+            Self::Sub(lhs, rhs) => lhs.evaluate(stats) - rhs.evaluate(stats),
+            Self::Mul(lhs, rhs) => lhs.evaluate(stats) * rhs.evaluate(stats),
+            Self::Div(lhs, rhs) => {
+                let divisor = rhs.evaluate(stats);
+                if divisor == 0.0 {
+                    0.0
+                } else {
+                    lhs.evaluate(stats) / divisor
+                }
+            }
+        }
+    }
+
+    /// Get every stat this formula reads from.
+    pub fn inputs(&self) -> Vec<StatName> {
+        match self {
+            Self::Constant(_) => vec![],
+            Self::Value(name) => vec![*name],
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::Div(lhs, rhs) => {
+                let mut inputs = lhs.inputs();
+                inputs.extend(rhs.inputs());
+                inputs
+            }
+        }
+    }
+}
+
+impl From<f64> for Formula {
+    fn from(value: f64) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl From<StatName> for Formula {
+    fn from(name: StatName) -> Self {
+        Self::Value(name)
+    }
+}
+
+impl<T: Into<Formula>> Add<T> for Formula {
+    type Output = Formula;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Self::Add(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl<T: Into<Formula>> Sub<T> for Formula {
+    type Output = Formula;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::Sub(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl<T: Into<Formula>> Mul<T> for Formula {
+    type Output = Formula;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::Mul(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl<T: Into<Formula>> Div<T> for Formula {
+    type Output = Formula;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self::Div(Box::new(self), Box::new(rhs.into()))
+    }
+}
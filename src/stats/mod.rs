@@ -1,13 +1,26 @@
+mod derivedstat;
+mod formula;
 mod modifier;
+mod overheal;
 mod stat;
+mod statevent;
 mod statgrowth;
 mod statkind;
 mod statname;
+#[allow(clippy::module_inception)]
 mod stats;
+mod statsdiff;
+mod statserror;
 
+pub use derivedstat::DerivedStat;
+pub use formula::Formula;
 pub use modifier::{Modifier, Modifiers};
+pub use overheal::Overheal;
 pub use stat::Stat;
+pub use statevent::StatEvent;
 pub use statgrowth::StatGrowth;
 pub use statkind::StatKind;
-pub use statname::StatName;
+pub use statname::{CustomStat, StatName};
 pub use stats::Stats;
+pub use statsdiff::{StatChange, StatDelta, StatsDiff};
+pub use statserror::StatsError;
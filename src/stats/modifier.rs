@@ -2,10 +2,10 @@ use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[allow(dead_code)]
-///     Represents a buff or a debuff that is applied to a specific stat.
+/// Represents a buff or a debuff that is applied to a specific stat.
 /// Buffs/Debuffs can be Additive, or Multiplicative and there can be no
 /// buffs/debuffs applied at all.
-///     These modifiers are used by certain items, skill, and equipment
+/// These modifiers are used by certain items, skill, and equipment
 /// in order to provide bonuses in and outside battle. For example the
 /// Annoy skill would double your attack but halve your defense. To do
 /// that, you would create Modifiers of Mult(1) on Attack and Mult(-0.5)
@@ -104,9 +104,11 @@ impl Modifiers {
 
     /// Removes one modifier to this stat. Allows modifiers to be stacked
     /// onto each other. If you remove Add(5) from Add(2), it becomes Add(-3).
+    /// Removing Mult(0.0) does nothing, as multiplying by zero cannot be undone.
     #[allow(dead_code)]
     pub fn remove(&mut self, modifier: Modifier) {
         match modifier {
+            Modifier::Mult(0.0) => {}
             Modifier::Mult(_) => self.multiplicative -= modifier,
             Modifier::Plus(_) => self.additive -= modifier,
             Modifier::None => {}
@@ -0,0 +1,12 @@
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Determines how far healing is allowed to raise the current value of a
+/// depletable stat past its maximum value.
+pub enum Overheal {
+    /// Healing stops at the maximum value of the stat.
+    #[default]
+    None,
+    /// Healing may exceed the maximum value by at most the inner amount.
+    Capped(usize),
+    /// Healing is never clamped.
+    Unlimited,
+}
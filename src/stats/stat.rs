@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::stats::{Modifier, Modifiers, StatKind, StatName};
+use crate::stats::{Modifier, Modifiers, Overheal, StatEvent, StatKind, StatName};
 
 use super::StatGrowth;
 
@@ -24,6 +24,11 @@ pub struct Stat {
 }
 
 impl Stat {
+    /// The highest value Friendship can reach.
+    pub const MAX_FRIENDSHIP: usize = 15;
+    /// The highest value every other stat can be set to.
+    pub const MAX_VALUE: usize = 999;
+
     #[allow(dead_code)]
     /// Create a base stat struct given its name, kind and modifiers present.
     pub fn new(name: StatName, kind: StatKind, stat_growth: StatGrowth) -> Self {
@@ -37,7 +42,7 @@ impl Stat {
 
     /// Get the base value of this stat. This is the value without any modifiers applied
     /// to it. It is returned as a usize instead of an f64 because base values cannot
-    /// be floating point numbers ever.
+    /// be floating point numbers ever. For StatKind::Depletable this is the current value.
     pub fn base(&self) -> usize {
         match self.kind {
             StatKind::Depletable(min, _) => min,
@@ -54,11 +59,7 @@ impl Stat {
     /// Other Stats: 0-999 -- 150
     #[allow(dead_code)]
     pub fn set_base(&mut self, value: usize) {
-        let condition = if self.name == StatName::Friendship {
-            value > 0 && value <= 15 // value to set is between 0 and 15 if the stat is Friendship.
-        } else {
-            value > 0 && value <= 999 // value to set is between 0 and 999 for every other stat.
-        };
+        let condition = value > 0 && value <= self.limit();
         self.kind = match self.kind {
             StatKind::Depletable(_, max) => {
                 if condition {
@@ -99,4 +100,218 @@ impl Stat {
 
         current_value
     }
+
+    /// Get the maximum value of this stat. For StatKind::Static this is the same as
+    /// the base value.
+    pub fn max(&self) -> usize {
+        self.kind.max_value()
+    }
+
+    /// Lowers the current value of a depletable stat by amount, stopping at zero.
+    /// Returns StatEvent::Depleted if this caused the stat to hit zero. Static stats
+    /// are left untouched.
+    pub fn damage(&mut self, amount: usize) -> Option<StatEvent> {
+        match self.kind {
+            StatKind::Depletable(current, max) => {
+                let value = current.saturating_sub(amount);
+                self.kind = StatKind::Depletable(value, max);
+                if current > 0 && value == 0 {
+                    Some(StatEvent::Depleted(self.name))
+                } else {
+                    None
+                }
+            }
+            StatKind::Static(_) => None,
+        }
+    }
+
+    /// Raises the current value of a depletable stat by amount. The overheal option
+    /// decides how far past the maximum value the stat may go. Healing never lowers
+    /// a value that was already raised past the cap. Returns StatEvent::Restored if
+    /// the stat was at zero before healing.
+    pub fn heal(&mut self, amount: usize, overheal: Overheal) -> Option<StatEvent> {
+        match self.kind {
+            StatKind::Depletable(current, max) => {
+                let cap = match overheal {
+                    Overheal::None => max,
+                    Overheal::Capped(extra) => max.saturating_add(extra),
+                    Overheal::Unlimited => usize::MAX,
+                };
+                let value = current.max(current.saturating_add(amount).min(cap));
+                self.kind = StatKind::Depletable(value, max);
+                if current == 0 && value > 0 {
+                    Some(StatEvent::Restored(self.name))
+                } else {
+                    None
+                }
+            }
+            StatKind::Static(_) => None,
+        }
+    }
+
+    /// Spends amount from the current value of a depletable stat, such as SkillPoints
+    /// for a skill. Returns false and leaves the stat untouched if there is not enough
+    /// to spend, or if the stat is not depletable.
+    pub fn spend(&mut self, amount: usize) -> bool {
+        match self.kind {
+            StatKind::Depletable(current, max) if current >= amount => {
+                self.kind = StatKind::Depletable(current - amount, max);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets the current value of a depletable stat to its maximum value. A stat that
+    /// is already past its maximum keeps its current value.
+    pub fn restore_full(&mut self) -> Option<StatEvent> {
+        let amount = self.max().saturating_sub(self.base());
+        self.heal(amount, Overheal::None)
+    }
+
+    /// Whether the current value of a depletable stat is zero. Static stats can never
+    /// be depleted.
+    pub fn is_depleted(&self) -> bool {
+        matches!(self.kind, StatKind::Depletable(0, _))
+    }
+
+    /// Get the current value of a depletable stat as a percentage of its maximum value,
+    /// between 0 and 100, or more if the stat is overhealed. Static stats are always at 100.
+    pub fn percent(&self) -> f64 {
+        match self.kind {
+            StatKind::Depletable(_, 0) => 0.0,
+            StatKind::Depletable(current, max) => current as f64 / max as f64 * 100.0,
+            StatKind::Static(_) => 100.0,
+        }
+    }
+
+    /// The highest value this stat can be set to: Stat::MAX_FRIENDSHIP for
+    /// Friendship and Stat::MAX_VALUE for every other stat.
+    pub fn limit(&self) -> usize {
+        if self.name == StatName::Friendship {
+            Self::MAX_FRIENDSHIP
+        } else {
+            Self::MAX_VALUE
+        }
+    }
+
+    /// Increases the stat by a roll of its growth, as happens on level up. Depletable
+    /// stats only increase their maximum value, so a depleted stat stays depleted.
+    /// Friendship never grows past Stat::MAX_FRIENDSHIP. Returns the increase.
+    pub fn grow(&mut self) -> usize {
+        let roll = self.stat_growth.roll().max(0) as usize;
+        let value = match self.kind {
+            StatKind::Depletable(_, max) => max,
+            StatKind::Static(value) => value,
+        };
+        let mut grown = value.saturating_add(roll);
+        if self.name == StatName::Friendship {
+            grown = grown.min(Self::MAX_FRIENDSHIP).max(value);
+        }
+        self.kind = match self.kind {
+            StatKind::Depletable(current, _) => StatKind::Depletable(current, grown),
+            StatKind::Static(_) => StatKind::Static(grown),
+        };
+        grown - value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::StatGrowth;
+
+    fn health(current: usize, max: usize) -> Stat {
+        Stat::new(
+            StatName::HealthPoints,
+            StatKind::Depletable(current, max),
+            StatGrowth::Slow,
+        )
+    }
+
+    #[test]
+    fn damage_stops_at_zero_and_reports_depletion_once() {
+        let mut stat = health(10, 20);
+        assert_eq!(stat.damage(4), None);
+        assert_eq!(stat.base(), 6);
+        assert_eq!(
+            stat.damage(50),
+            Some(StatEvent::Depleted(StatName::HealthPoints))
+        );
+        assert_eq!(stat.base(), 0);
+        assert_eq!(stat.damage(1), None);
+        assert!(stat.is_depleted());
+    }
+
+    #[test]
+    fn heal_is_capped_by_overheal() {
+        let mut stat = health(15, 20);
+        stat.heal(10, Overheal::None);
+        assert_eq!(stat.base(), 20);
+        stat.heal(10, Overheal::Capped(5));
+        assert_eq!(stat.base(), 25);
+        stat.heal(10, Overheal::Unlimited);
+        assert_eq!(stat.base(), 35);
+        stat.heal(1, Overheal::None);
+        assert_eq!(stat.base(), 35);
+    }
+
+    #[test]
+    fn heal_from_zero_reports_restoration() {
+        let mut stat = health(0, 20);
+        assert_eq!(
+            stat.heal(5, Overheal::None),
+            Some(StatEvent::Restored(StatName::HealthPoints))
+        );
+        assert_eq!(stat.heal(5, Overheal::None), None);
+    }
+
+    #[test]
+    fn spend_fails_without_enough() {
+        let mut stat = health(5, 20);
+        assert!(!stat.spend(6));
+        assert_eq!(stat.base(), 5);
+        assert!(stat.spend(5));
+        assert_eq!(stat.base(), 0);
+    }
+
+    #[test]
+    fn static_stats_ignore_depletable_operations() {
+        let mut stat = Stat::new(StatName::Attack, StatKind::Static(10), StatGrowth::Slow);
+        assert_eq!(stat.damage(5), None);
+        assert_eq!(stat.heal(5, Overheal::Unlimited), None);
+        assert!(!stat.spend(1));
+        assert_eq!(stat.base(), 10);
+        assert_eq!(stat.percent(), 100.0);
+    }
+
+    #[test]
+    fn restore_full_keeps_overheal() {
+        let mut stat = health(3, 20);
+        stat.restore_full();
+        assert_eq!(stat.base(), 20);
+        let mut stat = health(30, 20);
+        stat.restore_full();
+        assert_eq!(stat.base(), 30);
+    }
+
+    #[test]
+    fn growing_leaves_the_current_value_alone() {
+        let mut stat = health(0, 20);
+        stat.stat_growth = StatGrowth::Custom("5");
+        assert_eq!(stat.grow(), 5);
+        assert_eq!(stat.kind, StatKind::Depletable(0, 25));
+    }
+
+    #[test]
+    fn friendship_never_grows_past_its_cap() {
+        let mut stat = Stat::new(
+            StatName::Friendship,
+            StatKind::Static(13),
+            StatGrowth::Custom("5"),
+        );
+        assert_eq!(stat.grow(), 2);
+        assert_eq!(stat.base(), Stat::MAX_FRIENDSHIP);
+        assert_eq!(stat.grow(), 0);
+    }
 }
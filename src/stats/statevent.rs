@@ -0,0 +1,12 @@
+use crate::stats::StatName;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents a notable change in the value of a depletable stat. These are returned
+/// by the operations on Stat so that combat code can react to them, such as knocking
+/// out an entity once its HealthPoints have been depleted.
+pub enum StatEvent {
+    /// The current value of the stat has dropped to zero.
+    Depleted(StatName),
+    /// The current value of the stat has risen from zero.
+    Restored(StatName),
+}
@@ -1,13 +1,6 @@
 #![allow(dead_code)]
 
-use bracket_random::prelude::RandomNumberGenerator;
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
-
-/// The global static instance of the random number generator. This generator from bracket-lib has
-/// the features of using dice notation for its number generation, which make it perfect for stat growth.
-static RNG: Lazy<Mutex<RandomNumberGenerator>> =
-    Lazy::new(|| Mutex::new(RandomNumberGenerator::new()));
+use crate::random;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 /// Represents the speeds at which a stat can increase on level up.
@@ -30,11 +23,10 @@ impl StatGrowth {
     /// determines how much increase there is in the stat.
     pub fn roll(&self) -> i32 {
         match *self {
-            // Note: If something explodes, it's probably the Mutex.
-            Self::Fast => RNG.lock().unwrap().roll_str("3d2").unwrap(),
-            Self::Medium => RNG.lock().unwrap().roll_str("1d3").unwrap(),
-            Self::Slow => RNG.lock().unwrap().roll_str("1d2").unwrap(),
-            Self::Custom(dice) => RNG.lock().unwrap().roll_str(dice).unwrap(),
+            Self::Fast => random::roll("3d2"),
+            Self::Medium => random::roll("1d3"),
+            Self::Slow => random::roll("1d2"),
+            Self::Custom(dice) => random::roll(dice),
         }
     }
 }
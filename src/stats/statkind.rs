@@ -13,3 +13,22 @@ pub enum StatKind {
     /// are used. It holds its value that can't be depleted.
     Static(usize),
 }
+
+impl StatKind {
+    /// Get the maximum value held by this kind. For Static this is its value.
+    pub fn max_value(&self) -> usize {
+        match self {
+            Self::Depletable(_, max) => *max,
+            Self::Static(value) => *value,
+        }
+    }
+}
+
+impl std::fmt::Display for StatKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Depletable(current, max) => write!(f, "{}/{}", current, max),
+            Self::Static(value) => write!(f, "{}", value),
+        }
+    }
+}
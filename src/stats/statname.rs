@@ -1,3 +1,10 @@
+use once_cell::sync::Lazy;
+use std::{fmt, sync::Mutex};
+
+/// The global registry of user-defined stats. The index of a name in this list is the
+/// value stored in StatName::Custom, which keeps StatName small and Copy.
+static CUSTOM_STATS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 #[allow(dead_code)]
 /// Represents all possible stats present in the game. This only
@@ -13,4 +20,141 @@ pub enum StatName {
     Speed,
     Evasion,
     Friendship,
+    /// A user-defined stat such as Luck or Accuracy. Holds the id handed out by
+    /// StatName::custom, which is the only way to construct one.
+    Custom(CustomStat),
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// The id of a registered custom stat. It can only be created by StatName::custom, so
+/// every custom StatName refers to a registered name.
+pub struct CustomStat(usize);
+
+impl CustomStat {
+    /// The id of the stat read when indexing Stats by a custom stat it does not have.
+    /// StatName::custom never hands it out.
+    pub(crate) const MISSING: Self = Self(usize::MAX);
+
+    /// Get the position of the stat in the registry.
+    pub fn id(&self) -> usize {
+        self.0
+    }
+}
+
+impl StatName {
+    /// The number of built-in stats.
+    pub const COUNT: usize = 9;
+
+    /// Every built-in stat, in the order they are stored in Stats.
+    pub const BUILTIN: [StatName; Self::COUNT] = [
+        Self::HealthPoints,
+        Self::SkillPoints,
+        Self::Defense,
+        Self::SpecialDefense,
+        Self::Attack,
+        Self::SpecialAttack,
+        Self::Speed,
+        Self::Evasion,
+        Self::Friendship,
+    ];
+
+    /// Get the localization key of the name of this stat: stat.<name>, such as
+    /// stat.HealthPoints or stat.Luck.
+    pub fn key(&self) -> String {
+        format!("stat.{}", self)
+    }
+
+    /// Get the custom stat with the given name, registering it first if it does not
+    /// exist yet. Calling this twice with the same name returns the same StatName.
+    pub fn custom(name: &str) -> Self {
+        let mut registry = CUSTOM_STATS.lock().unwrap();
+        match registry.iter().position(|registered| registered == name) {
+            Some(id) => Self::Custom(CustomStat(id)),
+            None => {
+                registry.push(name.to_string());
+                Self::Custom(CustomStat(registry.len() - 1))
+            }
+        }
+    }
+
+    /// Looks up a stat by its name without registering anything. Built-in stats are
+    /// found by their variant name.
+    pub fn find(name: &str) -> Option<Self> {
+        if let Some(stat) = Self::BUILTIN.iter().find(|stat| stat.to_string() == name) {
+            return Some(*stat);
+        }
+        CUSTOM_STATS
+            .lock()
+            .unwrap()
+            .iter()
+            .position(|registered| registered == name)
+            .map(|id| Self::Custom(CustomStat(id)))
+    }
+
+    /// Get the position of a built-in stat in StatName::BUILTIN. Custom stats have no
+    /// fixed position.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::HealthPoints => Some(0),
+            Self::SkillPoints => Some(1),
+            Self::Defense => Some(2),
+            Self::SpecialDefense => Some(3),
+            Self::Attack => Some(4),
+            Self::SpecialAttack => Some(5),
+            Self::Speed => Some(6),
+            Self::Evasion => Some(7),
+            Self::Friendship => Some(8),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Whether this stat is one of the built-in stats.
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+}
+
+impl fmt::Display for StatName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(CustomStat(id)) => match CUSTOM_STATS.lock().unwrap().get(*id) {
+                Some(name) => write!(f, "{}", name),
+                None => write!(f, "Missing"),
+            },
+            builtin => write!(f, "{:?}", builtin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{Stat, StatGrowth, StatKind, Stats};
+
+    #[test]
+    fn custom_returns_the_same_stat_for_the_same_name() {
+        let luck = StatName::custom("TestLuck");
+        assert_eq!(StatName::custom("TestLuck"), luck);
+        assert_ne!(StatName::custom("TestCharm"), luck);
+        assert!(!luck.is_builtin());
+        assert_eq!(luck.to_string(), "TestLuck");
+    }
+
+    #[test]
+    fn find_does_not_register() {
+        assert_eq!(StatName::find("Attack"), Some(StatName::Attack));
+        assert_eq!(StatName::find("TestNeverRegistered"), None);
+        let fame = StatName::custom("TestFame");
+        assert_eq!(StatName::find("TestFame"), Some(fame));
+    }
+
+    #[test]
+    fn custom_stats_are_stored_alongside_builtin_ones() {
+        let luck = StatName::custom("TestStoredLuck");
+        let mut stats = Stats::default();
+        assert!(!stats.contains(luck));
+        stats.insert(Stat::new(luck, StatKind::Static(7), StatGrowth::Slow));
+        assert_eq!(stats.value(luck), 7.0);
+        assert_eq!(stats.iter().last().map(|stat| stat.name), Some(luck));
+    }
 }
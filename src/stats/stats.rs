@@ -1,19 +1,304 @@
-use crate::stats::{Stat, StatGrowth, StatKind, StatName};
-use std::ops::{Index, IndexMut};
+use crate::stats::{
+    CustomStat, DerivedStat, Formula, Modifier, Modifiers, Stat, StatChange, StatGrowth, StatKind,
+    StatName, StatsDiff, StatsError,
+};
+use std::{
+    fmt,
+    iter::Chain,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign},
+    slice::{Iter, IterMut},
+};
 
 #[derive(Debug, Clone, PartialEq)]
-/// Represents the stats of an entity. Certain restrictions are placed upon
-/// this struct, such that Vec<Stats>.len() == StatNameVariants. It contains
-/// a list of all valid stats that an entity can have.
+#[cfg_attr(feature = "ecs", derive(bevy_ecs::component::Component))]
+/// Represents the stats of an entity. Every built-in StatName is present exactly
+/// once, stored in a fixed-size array indexed by StatName::index, so looking them up
+/// is O(1) and can never fail. Custom stats registered through StatName::custom are
+/// stored after those. Some stats may be derived, meaning their value is calculated
+/// by a formula.
 pub struct Stats {
-    /// The list of valid stats.
-    pub stats: Vec<Stat>,
+    /// The built-in stats, in the order of StatName::BUILTIN.
+    builtin: [Stat; StatName::COUNT],
+    /// The custom stats, in the order they were added.
+    custom: Vec<Stat>,
+    /// The formulas of the derived stats. A derived stat is still stored as a regular
+    /// stat, its base value is simply overwritten whenever the formulas are recomputed.
+    derived: Vec<DerivedStat>,
 }
 
 impl Stats {
+    /// The stat read when indexing by a custom stat that was never added: a Static stat
+    /// of 0 without modifiers, shared by every missing stat.
+    pub const MISSING: Stat = Stat {
+        name: StatName::Custom(CustomStat::MISSING),
+        kind: StatKind::Static(0),
+        modifiers: Modifiers {
+            additive: Modifier::Plus(0.0),
+            multiplicative: Modifier::Mult(1.0),
+        },
+        stat_growth: StatGrowth::Slow,
+    };
+
+    /// Create a new instance of Stats given a list of stats. Any built-in stat missing
+    /// from the list keeps its default value, and later duplicates replace earlier ones.
     #[allow(dead_code)]
     pub fn new(stats: Vec<Stat>) -> Self {
-        Self { stats }
+        let mut new = Self::default();
+        stats.into_iter().for_each(|stat| {
+            new.insert(stat);
+        });
+        new
+    }
+
+    /// Adds a stat to this list, replacing and returning any stat with the same name.
+    /// This is how custom stats are given to an entity.
+    pub fn insert(&mut self, stat: Stat) -> Option<Stat> {
+        match self.get_mut(stat.name) {
+            Some(existing) => Some(std::mem::replace(existing, stat)),
+            None => {
+                self.custom.push(stat);
+                None
+            }
+        }
+    }
+
+    /// Get the stat with the given name, if this list has it. This always succeeds for
+    /// built-in stats.
+    pub fn get(&self, name: StatName) -> Option<&Stat> {
+        match name.index() {
+            Some(index) => Some(&self.builtin[index]),
+            None => self.custom.iter().find(|stat| stat.name == name),
+        }
+    }
+
+    /// Get a mutable reference to the stat with the given name, if this list has it.
+    /// This always succeeds for built-in stats.
+    pub fn get_mut(&mut self, name: StatName) -> Option<&mut Stat> {
+        match name.index() {
+            Some(index) => Some(&mut self.builtin[index]),
+            None => self.custom.iter_mut().find(|stat| stat.name == name),
+        }
+    }
+
+    /// Iterates over every stat, built-in stats first in the order of StatName::BUILTIN,
+    /// followed by custom stats in the order they were added.
+    pub fn iter(&self) -> Chain<Iter<'_, Stat>, Iter<'_, Stat>> {
+        self.builtin.iter().chain(self.custom.iter())
+    }
+
+    /// Iterates mutably over every stat, in the same order as Stats::iter.
+    pub fn iter_mut(&mut self) -> Chain<IterMut<'_, Stat>, IterMut<'_, Stat>> {
+        self.builtin.iter_mut().chain(self.custom.iter_mut())
+    }
+
+    /// Whether this list has a stat with the given name.
+    pub fn contains(&self, name: StatName) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Get the calculated value of a stat, or zero if this list does not have it.
+    pub fn value(&self, name: StatName) -> f64 {
+        self.get(name).map_or(0.0, Stat::value)
+    }
+
+    /// Sets the base value of a stat and recomputes the derived stats that depend on it.
+    pub fn set_base(&mut self, name: StatName, value: usize) {
+        if let Some(stat) = self.get_mut(name) {
+            stat.set_base(value);
+            self.refresh_derived();
+        }
+    }
+
+    /// Adds a modifier to a stat and recomputes the derived stats that depend on it.
+    pub fn add_modifier(&mut self, name: StatName, modifier: Modifier) {
+        if let Some(stat) = self.get_mut(name) {
+            stat.modifiers.add(modifier);
+            self.refresh_derived();
+        }
+    }
+
+    /// Removes a modifier from a stat and recomputes the derived stats that depend on it.
+    pub fn remove_modifier(&mut self, name: StatName, modifier: Modifier) {
+        if let Some(stat) = self.get_mut(name) {
+            stat.modifiers.remove(modifier);
+            self.refresh_derived();
+        }
+    }
+
+    /// Grows every stat by a roll of its growth, as happens on level up. Derived stats
+    /// are recalculated instead.
+    pub fn grow(&mut self) {
+        self.iter_mut().for_each(|stat| {
+            stat.grow();
+        });
+        self.refresh_derived();
+    }
+
+    /// Get the formulas of every derived stat.
+    pub fn derived(&self) -> &[DerivedStat] {
+        &self.derived
+    }
+
+    /// Makes a stat derived, calculating its value from the formula from now on. If the
+    /// stat is not present it is added as a static stat. Replaces any formula the stat
+    /// already had. Fails if the formula would make the stat depend on itself.
+    pub fn derive(&mut self, name: StatName, formula: Formula) -> Result<(), StatsError> {
+        for input in formula.inputs() {
+            if let Some(mut cycle) = self.find_path(input, name) {
+                cycle.insert(0, name);
+                return Err(StatsError::CyclicFormula(cycle));
+            }
+        }
+
+        if !self.contains(name) {
+            self.insert(Stat::new(name, StatKind::Static(0), StatGrowth::Slow));
+        }
+        self.derived.retain(|derived| derived.name != name);
+        self.derived.push(DerivedStat::new(name, formula));
+        self.refresh_derived();
+        Ok(())
+    }
+
+    /// Turns a derived stat back into a regular stat. It keeps its last calculated value.
+    pub fn remove_derived(&mut self, name: StatName) {
+        self.derived.retain(|derived| derived.name != name);
+    }
+
+    /// Recalculates the value of every derived stat. Inputs are always calculated before
+    /// the stats that read from them. The methods on Stats call this automatically, but
+    /// it must be called by hand after changing a stat through IndexMut.
+    pub fn refresh_derived(&mut self) {
+        for index in self.derived_order() {
+            let DerivedStat { name, formula } = &self.derived[index];
+            let (name, value) = (*name, formula.evaluate(self).max(0.0).round() as usize);
+            if let Some(stat) = self.get_mut(name) {
+                stat.kind = match stat.kind {
+                    StatKind::Depletable(current, _) => {
+                        StatKind::Depletable(current.min(value), value)
+                    }
+                    StatKind::Static(_) => StatKind::Static(value),
+                };
+            }
+        }
+    }
+
+    /// Get the formula of a stat, if it is derived.
+    fn formula(&self, name: StatName) -> Option<&Formula> {
+        self.derived
+            .iter()
+            .find(|derived| derived.name == name)
+            .map(|derived| &derived.formula)
+    }
+
+    /// Finds a chain of formula inputs leading from one stat to another. Returns the
+    /// chain including both ends.
+    fn find_path(&self, from: StatName, to: StatName) -> Option<Vec<StatName>> {
+        if from == to {
+            return Some(vec![from]);
+        }
+        let formula = self.formula(from)?;
+        formula.inputs().into_iter().find_map(|input| {
+            self.find_path(input, to).map(|mut path| {
+                path.insert(0, from);
+                path
+            })
+        })
+    }
+
+    /// Get the indices of the derived stats, ordered so that every stat comes after the
+    /// derived stats its formula reads from.
+    fn derived_order(&self) -> Vec<usize> {
+        fn visit(stats: &Stats, index: usize, order: &mut Vec<usize>) {
+            if order.contains(&index) {
+                return;
+            }
+            for input in stats.derived[index].formula.inputs() {
+                if let Some(input) = stats
+                    .derived
+                    .iter()
+                    .position(|derived| derived.name == input)
+                {
+                    visit(stats, input, order);
+                }
+            }
+            order.push(index);
+        }
+
+        let mut order = Vec::with_capacity(self.derived.len());
+        (0..self.derived.len()).for_each(|index| visit(self, index, &mut order));
+        order
+    }
+
+    /// Get the difference between these stats and other, such as the stats of an entity
+    /// before and after equipping an item. Every stat present in either is included.
+    pub fn diff(&self, other: &Stats) -> StatsDiff {
+        let mut changes = self
+            .iter()
+            .map(|stat| StatChange {
+                name: stat.name,
+                before: stat.kind.clone(),
+                after: other
+                    .get(stat.name)
+                    .map_or(zeroed(&stat.kind), |s| s.kind.clone()),
+            })
+            .collect::<Vec<_>>();
+        changes.extend(
+            other
+                .iter()
+                .filter(|stat| !self.contains(stat.name))
+                .map(|stat| StatChange {
+                    name: stat.name,
+                    before: zeroed(&stat.kind),
+                    after: stat.kind.clone(),
+                }),
+        );
+        StatsDiff { changes }
+    }
+
+    /// Combines two stats, applying f to the base values of each pair of stats with
+    /// the same name. Stats only present in rhs are combined with a zeroed copy. For
+    /// two depletable stats, f is applied to the current values and to the maximum
+    /// values separately, so a bonus of 50 HealthPoints raises both.
+    fn combine(
+        &self,
+        rhs: &Stats,
+        f: impl Fn(usize, usize) -> usize,
+        modifiers: impl Fn(&mut Modifiers, &Modifiers),
+    ) -> Stats {
+        let mut stats = self.clone();
+        for other in rhs {
+            if !stats.contains(other.name) {
+                stats.insert(Stat::new(
+                    other.name,
+                    zeroed(&other.kind),
+                    other.stat_growth,
+                ));
+            }
+            if let Some(stat) = stats.get_mut(other.name) {
+                stat.kind = match (&stat.kind, &other.kind) {
+                    (StatKind::Depletable(c1, m1), StatKind::Depletable(c2, m2)) => {
+                        StatKind::Depletable(f(*c1, *c2), f(*m1, *m2))
+                    }
+                    (StatKind::Depletable(current, max), StatKind::Static(value)) => {
+                        StatKind::Depletable(f(*current, *value), f(*max, *value))
+                    }
+                    (StatKind::Static(value), other) => {
+                        StatKind::Static(f(*value, other.max_value()))
+                    }
+                };
+                modifiers(&mut stat.modifiers, &other.modifiers);
+            }
+        }
+        stats.refresh_derived();
+        stats
+    }
+}
+
+/// Get a copy of a kind of stat with every value set to zero.
+fn zeroed(kind: &StatKind) -> StatKind {
+    match kind {
+        StatKind::Depletable(_, _) => StatKind::Depletable(0, 0),
+        StatKind::Static(_) => StatKind::Static(0),
     }
 }
 
@@ -21,37 +306,360 @@ impl Default for Stats {
     fn default() -> Self {
         use self::{StatKind::*, StatName::*};
         Self {
-            stats: vec![
-                Stat::new(HealthPoints, Depletable(0, 0), StatGrowth::Slow),
-                Stat::new(SkillPoints, Depletable(0, 0), StatGrowth::Slow),
-                Stat::new(Defense, Static(0), StatGrowth::Slow),
-                Stat::new(SpecialDefense, Static(0), StatGrowth::Slow),
-                Stat::new(Attack, Static(0), StatGrowth::Slow),
-                Stat::new(SpecialAttack, Static(0), StatGrowth::Slow),
-                Stat::new(Speed, Static(0), StatGrowth::Slow),
-                Stat::new(Evasion, Static(0), StatGrowth::Slow),
-                Stat::new(Friendship, Static(0), StatGrowth::Slow),
-            ],
+            builtin: StatName::BUILTIN.map(|name| {
+                let kind = match name {
+                    HealthPoints | SkillPoints => Depletable(0, 0),
+                    _ => Static(0),
+                };
+                Stat::new(name, kind, StatGrowth::Slow)
+            }),
+            custom: Vec::new(),
+            derived: Vec::new(),
         }
     }
 }
 
+impl<'a> IntoIterator for &'a Stats {
+    type Item = &'a Stat;
+    type IntoIter = Chain<Iter<'a, Stat>, Iter<'a, Stat>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Stats {
+    type Item = &'a mut Stat;
+    type IntoIter = Chain<IterMut<'a, Stat>, IterMut<'a, Stat>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Indexing never fails. Built-in stats are always present, and a custom stat that was
+/// never added reads as Stats::MISSING. Use Stats::get to tell the two apart.
 impl Index<StatName> for Stats {
     type Output = Stat;
 
     fn index(&self, index: StatName) -> &Self::Output {
-        match self.stats.iter().find(|&stat| stat.name == index) {
-            Some(value) => value,
-            None => panic!("Invalid Stat {:#?}", index),
+        match index.index() {
+            Some(builtin) => &self.builtin[builtin],
+            None => self
+                .custom
+                .iter()
+                .find(|stat| stat.name == index)
+                .unwrap_or(&Self::MISSING),
         }
     }
 }
 
+/// Mutably indexing by a custom stat that was never added adds it as a Static stat of 0.
 impl IndexMut<StatName> for Stats {
     fn index_mut(&mut self, index: StatName) -> &mut Self::Output {
-        match self.stats.iter_mut().find(|stat| stat.name == index) {
-            Some(value) => value,
-            None => panic!("Invalid Stat {:#?}", index),
+        match index.index() {
+            Some(builtin) => &mut self.builtin[builtin],
+            None => {
+                let position = match self.custom.iter().position(|stat| stat.name == index) {
+                    Some(position) => position,
+                    None => {
+                        self.custom
+                            .push(Stat::new(index, StatKind::Static(0), StatGrowth::Slow));
+                        self.custom.len() - 1
+                    }
+                };
+                &mut self.custom[position]
+            }
         }
     }
 }
+
+/// Adds the base values of every stat and stacks their modifiers. This is how the
+/// bonuses of an item are added to the stats of an entity. Depletable stats add both
+/// their current and maximum values, saturating at usize::MAX.
+impl Add<&Stats> for &Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: &Stats) -> Self::Output {
+        self.combine(
+            rhs,
+            |x, y| x.saturating_add(y),
+            |modifiers, other| {
+                modifiers.add(other.additive);
+                modifiers.add(other.multiplicative);
+            },
+        )
+    }
+}
+
+impl Add for Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl AddAssign<&Stats> for Stats {
+    fn add_assign(&mut self, rhs: &Stats) {
+        *self = &*self + rhs;
+    }
+}
+
+/// Subtracts the base values of every stat and removes the modifiers of rhs.
+/// Depletable stats subtract both their current and maximum values. Every value stops
+/// at zero, so this only undoes Add when nothing was clamped: taking a bonus of 50
+/// HealthPoints away from an entity with 10 left leaves it at 0. Mult(0.0) modifiers
+/// are not removed, see Modifiers::remove.
+impl Sub<&Stats> for &Stats {
+    type Output = Stats;
+
+    fn sub(self, rhs: &Stats) -> Self::Output {
+        self.combine(
+            rhs,
+            |x, y| x.saturating_sub(y),
+            |modifiers, other| {
+                modifiers.remove(other.additive);
+                modifiers.remove(other.multiplicative);
+            },
+        )
+    }
+}
+
+impl Sub for Stats {
+    type Output = Stats;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl SubAssign<&Stats> for Stats {
+    fn sub_assign(&mut self, rhs: &Stats) {
+        *self = &*self - rhs;
+    }
+}
+
+/// Scales the base value of every stat by a factor, rounding to the nearest whole
+/// number. Modifiers are left untouched.
+impl Mul<f64> for &Stats {
+    type Output = Stats;
+
+    fn mul(self, factor: f64) -> Self::Output {
+        let scale = |value: usize| (value as f64 * factor).max(0.0).round() as usize;
+        let mut stats = self.clone();
+        stats.iter_mut().for_each(|stat| {
+            stat.kind = match stat.kind {
+                StatKind::Depletable(current, max) => {
+                    StatKind::Depletable(scale(current), scale(max))
+                }
+                StatKind::Static(value) => StatKind::Static(scale(value)),
+            }
+        });
+        stats.refresh_derived();
+        stats
+    }
+}
+
+impl Mul<f64> for Stats {
+    type Output = Stats;
+
+    fn mul(self, factor: f64) -> Self::Output {
+        &self * factor
+    }
+}
+
+impl MulAssign<f64> for Stats {
+    fn mul_assign(&mut self, factor: f64) {
+        *self = &*self * factor;
+    }
+}
+
+/// Displays the stats as a table of their base and calculated values.
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{:>12}{:>12}", "Stat", "Base", "Value")?;
+        for stat in self {
+            writeln!(
+                f,
+                "{:<16}{:>12}{:>12.1}",
+                stat.name.to_string(),
+                stat.kind.to_string(),
+                stat.value()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::StatDelta;
+
+    fn with(values: &[(StatName, usize)]) -> Stats {
+        let mut stats = Stats::default();
+        for &(name, value) in values {
+            stats[name].kind = match name {
+                StatName::HealthPoints | StatName::SkillPoints => {
+                    StatKind::Depletable(value, value)
+                }
+                _ => StatKind::Static(value),
+            };
+        }
+        stats
+    }
+
+    #[test]
+    fn derived_stats_follow_their_inputs() {
+        let mut stats = with(&[(StatName::Attack, 10)]);
+        stats
+            .derive(
+                StatName::SpecialAttack,
+                Formula::from(StatName::Attack) * 2.0,
+            )
+            .unwrap();
+        assert_eq!(stats.value(StatName::SpecialAttack), 20.0);
+        stats.set_base(StatName::Attack, 15);
+        assert_eq!(stats.value(StatName::SpecialAttack), 30.0);
+        stats.add_modifier(StatName::Attack, Modifier::Plus(5.0));
+        assert_eq!(stats.value(StatName::SpecialAttack), 40.0);
+    }
+
+    #[test]
+    fn derived_stats_are_computed_after_their_derived_inputs() {
+        let mut stats = with(&[(StatName::Speed, 10)]);
+        stats
+            .derive(StatName::Defense, Formula::from(StatName::Evasion) + 1.0)
+            .unwrap();
+        stats
+            .derive(StatName::Evasion, Formula::from(StatName::Speed) / 2.0)
+            .unwrap();
+        assert_eq!(stats.value(StatName::Evasion), 5.0);
+        assert_eq!(stats.value(StatName::Defense), 6.0);
+    }
+
+    #[test]
+    fn cyclic_formulas_are_rejected() {
+        let mut stats = Stats::default();
+        stats
+            .derive(StatName::Defense, Formula::from(StatName::Attack))
+            .unwrap();
+        assert_eq!(
+            stats.derive(StatName::Attack, Formula::from(StatName::Defense) + 1.0),
+            Err(StatsError::CyclicFormula(vec![
+                StatName::Attack,
+                StatName::Defense,
+                StatName::Attack
+            ]))
+        );
+        assert!(stats
+            .derive(StatName::Speed, Formula::from(StatName::Speed))
+            .is_err());
+        assert_eq!(stats.derived().len(), 1);
+    }
+
+    #[test]
+    fn dividing_by_zero_evaluates_to_zero() {
+        let mut stats = with(&[(StatName::Attack, 10)]);
+        stats
+            .derive(
+                StatName::Evasion,
+                Formula::from(StatName::Attack) / StatName::Speed,
+            )
+            .unwrap();
+        assert_eq!(stats.value(StatName::Evasion), 0.0);
+    }
+
+    #[test]
+    fn every_builtin_stat_is_present() {
+        let stats = Stats::default();
+        for name in StatName::BUILTIN {
+            assert_eq!(stats[name].name, name);
+        }
+        assert_eq!(stats.iter().count(), StatName::COUNT);
+    }
+
+    #[test]
+    fn indexing_a_missing_custom_stat_reads_zero() {
+        let luck = StatName::custom("TestIndexLuck");
+        let stats = Stats::default();
+        assert_eq!(stats[luck].base(), 0);
+        assert_eq!(stats[luck], Stats::MISSING);
+        assert!(stats.get(luck).is_none());
+    }
+
+    #[test]
+    fn mutably_indexing_a_missing_custom_stat_adds_it() {
+        let luck = StatName::custom("TestIndexMutLuck");
+        let mut stats = Stats::default();
+        stats[luck].set_base(3);
+        assert_eq!(stats.get(luck).map(Stat::base), Some(3));
+        assert_eq!(stats.iter().count(), StatName::COUNT + 1);
+    }
+
+    #[test]
+    fn adding_raises_current_and_maximum_values() {
+        let stats = with(&[(StatName::HealthPoints, 40), (StatName::Attack, 10)]);
+        let bonus = with(&[(StatName::HealthPoints, 50), (StatName::Attack, 5)]);
+        let total = &stats + &bonus;
+        assert_eq!(
+            total[StatName::HealthPoints].kind,
+            StatKind::Depletable(90, 90)
+        );
+        assert_eq!(total.value(StatName::Attack), 15.0);
+    }
+
+    #[test]
+    fn subtracting_clamps_at_zero() {
+        let mut stats = with(&[(StatName::HealthPoints, 100), (StatName::Attack, 10)]);
+        stats[StatName::HealthPoints].damage(90);
+        let bonus = with(&[(StatName::HealthPoints, 50), (StatName::Attack, 5)]);
+        let rest = &stats - &bonus;
+        assert_eq!(
+            rest[StatName::HealthPoints].kind,
+            StatKind::Depletable(0, 50)
+        );
+        assert_eq!(rest.value(StatName::Attack), 5.0);
+    }
+
+    #[test]
+    fn subtracting_undoes_adding_without_clamping() {
+        let mut stats = with(&[(StatName::Attack, 10)]);
+        stats.add_modifier(StatName::Attack, Modifier::Mult(2.0));
+        let mut bonus = with(&[(StatName::Attack, 5)]);
+        bonus.add_modifier(StatName::Attack, Modifier::Plus(3.0));
+        assert_eq!(&(&stats + &bonus) - &bonus, stats);
+    }
+
+    #[test]
+    fn removing_a_zero_multiplier_does_nothing() {
+        let mut stats = with(&[(StatName::Attack, 10)]);
+        stats.remove_modifier(StatName::Attack, Modifier::Mult(0.0));
+        assert_eq!(stats[StatName::Attack].modifiers, Modifiers::default());
+    }
+
+    #[test]
+    fn scaling_rounds_every_value() {
+        let stats = with(&[(StatName::HealthPoints, 15), (StatName::Attack, 3)]) * 1.5;
+        assert_eq!(
+            stats[StatName::HealthPoints].kind,
+            StatKind::Depletable(23, 23)
+        );
+        assert_eq!(stats[StatName::Attack].base(), 5);
+    }
+
+    #[test]
+    fn diff_reports_the_change_of_every_stat() {
+        let before = with(&[(StatName::HealthPoints, 10), (StatName::Attack, 4)]);
+        let after = with(&[(StatName::HealthPoints, 12), (StatName::Attack, 3)]);
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.delta(StatName::HealthPoints),
+            StatDelta::Depletable(2, 2)
+        );
+        assert_eq!(diff.delta(StatName::Attack), StatDelta::Static(-1));
+        assert!(diff.delta(StatName::Speed).is_zero());
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::stats::{StatKind, StatName};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// Represents how much the base value of a single stat changed. Mirrors StatKind,
+/// so depletable stats report the change of both their current and maximum value.
+pub enum StatDelta {
+    /// Holds the change of the current value and of the maximum value.
+    Depletable(isize, isize),
+    /// Holds the change of the value.
+    Static(isize),
+}
+
+impl StatDelta {
+    /// Get the change between two kinds of a stat. If the kinds do not match, the
+    /// change of the maximum value is reported as a static change.
+    pub fn between(before: &StatKind, after: &StatKind) -> Self {
+        let change = |before: usize, after: usize| after as isize - before as isize;
+        match (before, after) {
+            (StatKind::Depletable(c1, m1), StatKind::Depletable(c2, m2)) => {
+                Self::Depletable(change(*c1, *c2), change(*m1, *m2))
+            }
+            (StatKind::Static(v1), StatKind::Static(v2)) => Self::Static(change(*v1, *v2)),
+            (before, after) => Self::Static(change(before.max_value(), after.max_value())),
+        }
+    }
+
+    /// Whether the stat did not change at all.
+    pub fn is_zero(&self) -> bool {
+        matches!(self, Self::Depletable(0, 0) | Self::Static(0))
+    }
+}
+
+impl fmt::Display for StatDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Depletable(current, max) => write!(f, "{:+}/{:+}", current, max),
+            Self::Static(value) => write!(f, "{:+}", value),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Represents the change of a single stat between two instances of Stats.
+pub struct StatChange {
+    /// The stat that changed.
+    pub name: StatName,
+    /// The kind of the stat before the change.
+    pub before: StatKind,
+    /// The kind of the stat after the change.
+    pub after: StatKind,
+}
+
+impl StatChange {
+    /// Get how much the stat changed.
+    pub fn delta(&self) -> StatDelta {
+        StatDelta::between(&self.before, &self.after)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+/// Represents the per stat difference between two instances of Stats, such as the
+/// stats of an entity before and after a level up. Created with Stats::diff and
+/// displayed as a table of the before, after and change columns.
+pub struct StatsDiff {
+    /// The change of every stat present in either instance, in the order of Stats::iter.
+    pub changes: Vec<StatChange>,
+}
+
+impl StatsDiff {
+    /// Get the change of the stat with the given name.
+    pub fn get(&self, name: StatName) -> Option<&StatChange> {
+        self.changes.iter().find(|change| change.name == name)
+    }
+
+    /// Get how much the stat with the given name changed. Stats that are not part of
+    /// this diff did not change.
+    pub fn delta(&self, name: StatName) -> StatDelta {
+        self.get(name)
+            .map_or(StatDelta::Static(0), StatChange::delta)
+    }
+
+    /// Whether no stat changed at all.
+    pub fn is_empty(&self) -> bool {
+        self.changes.iter().all(|change| change.delta().is_zero())
+    }
+}
+
+impl fmt::Display for StatsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16}{:>12}{:>12}{:>12}",
+            "Stat", "Before", "After", "Change"
+        )?;
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{:<16}{:>12}{:>12}{:>12}",
+                change.name.to_string(),
+                change.before.to_string(),
+                change.after.to_string(),
+                change.delta().to_string()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::stats::StatName;
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// Represents the ways an operation on Stats can fail.
+pub enum StatsError {
+    /// Registering the formula of the derived stat would make it depend on itself.
+    /// Holds the chain of stats that forms the cycle.
+    CyclicFormula(Vec<StatName>),
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CyclicFormula(cycle) => {
+                let cycle = cycle
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "Derived stat formulas form a cycle: {}",
+                    cycle.join(" -> ")
+                )
+            }
+        }
+    }
+}
+
+impl Error for StatsError {}
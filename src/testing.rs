@@ -0,0 +1,83 @@
+//! Builders shared by the unit tests of every module.
+
+use crate::{
+    entities::{ClassBuilder, EntityBuilder},
+    ids::TemplateId,
+    levels::LevelData,
+    misc::{
+        Equipment, EquipmentType, Inventory, Item, ItemBuilder, ItemKind, Weapon, WeaponCategory,
+    },
+    stats::{StatKind, StatName, Stats},
+};
+
+/// Get default stats with the given base values. HealthPoints and SkillPoints start
+/// full.
+pub fn stats(values: &[(StatName, usize)]) -> Stats {
+    let mut stats = Stats::default();
+    for &(name, value) in values {
+        stats[name].kind = match name {
+            StatName::HealthPoints | StatName::SkillPoints => StatKind::Depletable(value, value),
+            _ => StatKind::Static(value),
+        };
+    }
+    stats
+}
+
+/// Get a builder for a level 1 entity with 100 HealthPoints, 20 SkillPoints and 10
+/// Attack, wearing nothing.
+pub fn entity(name: &'static str) -> EntityBuilder {
+    EntityBuilder::default()
+        .name(name)
+        .stats(stats(&[
+            (StatName::HealthPoints, 100),
+            (StatName::SkillPoints, 20),
+            (StatName::Attack, 10),
+        ]))
+        .level_data(LevelData::new(1, 0))
+        .equipment(Equipment::new(vec![]).unwrap())
+}
+
+/// Get a builder for an item of the given kind and template with no stats. Weapons
+/// get a sword profile.
+pub fn item(kind: ItemKind, template: usize) -> ItemBuilder {
+    let builder = ItemBuilder::default()
+        .name("Item")
+        .desc("")
+        .kind(kind)
+        .template(TemplateId(template))
+        .stats(Stats::default());
+    match kind {
+        ItemKind::Equipment(EquipmentType::Weapon) => {
+            builder.weapon(Some(Weapon::new(WeaponCategory::Sword, 10)))
+        }
+        _ => builder,
+    }
+}
+
+/// Get a piece of equipment for the slot with the given stats.
+pub fn equipment(slot: EquipmentType, values: &[(StatName, usize)]) -> Item {
+    item(ItemKind::Equipment(slot), slot as usize + 100)
+        .stats(stats(values))
+        .build()
+        .unwrap()
+}
+
+/// Get an inventory holding the given quantity of materials of every template.
+pub fn materials(materials: &[(usize, usize)]) -> Inventory {
+    let items = materials
+        .iter()
+        .flat_map(|&(template, quantity)| {
+            (0..quantity).map(move |_| item(ItemKind::KeyItem, template).build().unwrap())
+        })
+        .collect();
+    Inventory::new(items).unwrap()
+}
+
+/// Get a builder for a class with the stats of testing::entity.
+pub fn class(name: &'static str) -> ClassBuilder {
+    ClassBuilder::default().name(name).base_stats(stats(&[
+        (StatName::HealthPoints, 100),
+        (StatName::SkillPoints, 20),
+        (StatName::Attack, 10),
+    ]))
+}